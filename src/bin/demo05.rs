@@ -7,12 +7,15 @@ fn main() {
 }
 
 fn demo_cra() {
-    let res = cdm::ch05::chinese_remainder_algorithm(&[5, 7], &[1, 3]);
+    let res = cdm::ch05::chinese_remainder_algorithm(&[5, 7], &[1, 3]).unwrap();
     info!("res = {res}");
     println!();
     println!();
     println!();
     println!();
-    let res = cdm::ch05::chinese_remainder_algorithm(&[3, 4, 5], &[2, 3, 1]);
+    let res = cdm::ch05::chinese_remainder_algorithm(&[3, 4, 5], &[2, 3, 1]).unwrap();
     info!("res = {res}");
+    println!();
+    let res = cdm::ch05::generalized_chinese_remainder_algorithm(&[4, 6], &[3, 5]);
+    info!("res = {res:?}");
 }
@@ -91,3 +91,15 @@ impl<D: EuclideanDomain + PartialEq + std::fmt::Debug> ExtendedEuclideanAlgorith
         (f % gcd.clone()).is_zero() && (g % gcd.clone()).is_zero()
     }
 }
+
+/// The greatest common divisor of `f` and `g` in normal form, that is
+/// `normal(r_l)` from the [`extended_euclidean_algorithm`].
+pub fn gcd<D: EuclideanDomain + PartialEq + std::fmt::Debug>(f: &D, g: &D) -> D {
+    if f.is_zero() {
+        g.normal()
+    } else if g.is_zero() {
+        f.normal()
+    } else {
+        extended_euclidean_algorithm(f, g).gcd().normal()
+    }
+}
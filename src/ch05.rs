@@ -2,10 +2,126 @@
 
 use tracing::debug;
 
-use crate::{ch03::extended_euclidean_algorithm, euclidean_domain::EuclideanDomain};
+use crate::{
+    ch03::{extended_euclidean_algorithm, gcd},
+    euclidean_domain::EuclideanDomain,
+    field::Field,
+    fractions::{Fraction, RationalFunction},
+    newton_interpolation::NewtonInterpolation,
+    Group, Integer, Natural, Polynomial, Rational, Ring,
+};
+
+/// Why a system of congruences `a ≡ vᵢ mod mᵢ` could not be solved.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CraError<R> {
+    /// The numbers of moduli and values differ.
+    LengthMismatch { moduli: usize, values: usize },
+    /// The modulus `mᵢ` is zero.
+    ZeroModulus { i: usize },
+    /// The moduli `mᵢ` and `mⱼ` have the nontrivial common factor `gcd`.
+    NotCoprime { i: usize, j: usize, gcd: R },
+    /// `vᵢ ≢ vⱼ mod gcd(mᵢ, mⱼ)`, so the congruences have no common solution.
+    Incompatible { i: usize, j: usize, gcd: R },
+}
+
+fn check_lengths<R>(ms: &[R], v: &[R]) -> Result<(), CraError<R>> {
+    if ms.len() == v.len() {
+        Ok(())
+    } else {
+        Err(CraError::LengthMismatch {
+            moduli: ms.len(),
+            values: v.len(),
+        })
+    }
+}
+
+/// The basis `eᵢ ≡ 1 mod mᵢ`, `eᵢ ≡ 0 mod mⱼ` for `j ≠ i` of the Chinese
+/// remainder theorem for pairwise coprime moduli `m₀, ..., mᵣ₋₁`, which
+/// reconstructs `Σ vᵢ eᵢ mod m` for any number of value vectors `v`.
+#[derive(Debug, Clone)]
+pub struct CrtBasis<R> {
+    moduli: Vec<R>,
+    basis: Vec<R>,
+    m: R,
+}
+
+impl<R: EuclideanDomain> CrtBasis<R> {
+    /// Steps 1 and 2 of the CRA: `m = m₀ ··· mᵣ₋₁` and `eᵢ = sᵢ m/mᵢ` with
+    /// `sᵢ (m/mᵢ) + tᵢ mᵢ = 1` from the extended Euclidean algorithm.
+    pub fn new(ms: &[R]) -> Result<Self, CraError<R>> {
+        if let Some(i) = ms.iter().position(|m| m.is_zero()) {
+            return Err(CraError::ZeroModulus { i });
+        }
+        for (i, mi) in ms.iter().enumerate() {
+            for (j, mj) in ms.iter().enumerate().skip(i + 1) {
+                let gcd = gcd(mi, mj);
+                if !gcd.is_one() {
+                    return Err(CraError::NotCoprime { i, j, gcd });
+                }
+            }
+        }
+
+        let m = ms.iter().fold(R::one(), |m, mi| m * mi.clone()).normal();
+        debug!("m = {m:?}");
+        let basis = ms
+            .iter()
+            .enumerate()
+            .map(|(i, mi)| {
+                let cofactor = m.clone() / mi.clone();
+                let eea = extended_euclidean_algorithm(&cofactor, mi);
+                let l = eea.r.len() - 2;
+                let s = eea.s[l].clone()
+                    * eea.r[l]
+                        .multiplicative_inverse()
+                        .expect("the moduli are coprime");
+                let e = ((s % mi.clone()) * cofactor).rem_canonical(&m);
+                debug!("e{i} = {e:?}");
+                e
+            })
+            .collect();
+
+        Ok(CrtBasis {
+            moduli: ms.to_vec(),
+            basis,
+            m,
+        })
+    }
+
+    /// The product `m` of the moduli.
+    pub fn modulus(&self) -> &R {
+        &self.m
+    }
+
+    /// Step 3 of the CRA: the unique `a` with `a ≡ vᵢ mod mᵢ` and `d(a) < d(m)`.
+    pub fn reconstruct(&self, v: &[R]) -> Result<R, CraError<R>> {
+        check_lengths(&self.moduli, v)?;
+
+        let a = v
+            .iter()
+            .zip(&self.moduli)
+            .zip(&self.basis)
+            .fold(R::zero(), |a, ((vi, mi), ei)| {
+                a + (vi.clone() % mi.clone()) * ei.clone()
+            });
+        Ok(a.rem_canonical(&self.m))
+    }
+}
 
 /// Algorithm 5.4 Chinese Remainder Algorithm (CRA).
-pub fn chinese_remainder_algorithm<R: EuclideanDomain + PartialOrd>(ms: &[R], v: &[R]) -> R {
+///
+/// - Input: pairwise coprime nonzero `m₀, ..., mᵣ₋₁` and `v₀, ..., vᵣ₋₁`.
+/// - Output: the unique `a` with `a ≡ vᵢ mod mᵢ` and `d(a) < d(m)` for
+///   `m = m₀ ··· mᵣ₋₁`.
+///
+/// The result is the [`EuclideanDomain::rem_canonical`] representative, so
+/// `0 ≤ a < m` for integers and `deg a < deg m` for polynomials.
+///
+/// For repeated reconstruction with the same moduli, precompute the
+/// [`CrtBasis`] instead.
+pub fn chinese_remainder_algorithm<R: EuclideanDomain>(
+    ms: &[R],
+    v: &[R],
+) -> Result<R, CraError<R>> {
     let scope = tracing::span!(
         tracing::Level::DEBUG,
         "(5.4) CRA",
@@ -14,28 +130,385 @@ pub fn chinese_remainder_algorithm<R: EuclideanDomain + PartialOrd>(ms: &[R], v:
     );
     let _enter = scope.enter();
 
-    let mut c = vec![];
-    let m = ms.iter().cloned().reduce(|m0, m1| m0 * m1).unwrap();
-    debug!("m = {m:?}");
+    check_lengths(ms, v)?;
+    let a = CrtBasis::new(ms)?.reconstruct(v)?;
+    debug!("a = {a:?}");
+    Ok(a)
+}
+
+/// The Chinese remainder algorithm for arbitrary nonzero moduli.
+///
+/// - Input: nonzero `m₀, ..., mᵣ₋₁` and `v₀, ..., vᵣ₋₁`.
+/// - Output: `(a, l)` with `l = lcm(m₀, ..., mᵣ₋₁)` and the unique `a`
+///   with `a ≡ vᵢ mod mᵢ` and `d(a) < d(l)`, if the congruences are pairwise
+///   compatible, that is `vᵢ ≡ vⱼ mod gcd(mᵢ, mⱼ)`.
+///
+/// The congruences are merged one at a time: given `a ≡ v mod n` with
+/// `g = gcd(n, mᵢ) = sn + tmᵢ`, the solution `a + sn (vᵢ - a)/g` is unique
+/// modulo `lcm(n, mᵢ)`.
+pub fn generalized_chinese_remainder_algorithm<R: EuclideanDomain>(
+    ms: &[R],
+    v: &[R],
+) -> Result<(R, R), CraError<R>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "Generalized CRA",
+        ms = format!("{ms:?}"),
+        v = format!("{v:?}"),
+    );
+    let _enter = scope.enter();
+
+    check_lengths(ms, v)?;
+    if let Some(i) = ms.iter().position(|m| m.is_zero()) {
+        return Err(CraError::ZeroModulus { i });
+    }
+    for (i, (mi, vi)) in ms.iter().zip(v).enumerate() {
+        for (j, (mj, vj)) in ms.iter().zip(v).enumerate().skip(i + 1) {
+            let gcd = gcd(mi, mj);
+            if !((vi.clone() - vj.clone()) % gcd.clone()).is_zero() {
+                return Err(CraError::Incompatible { i, j, gcd });
+            }
+        }
+    }
+
+    let mut a = R::zero();
+    let mut n = R::one();
+    for (mi, vi) in ms.iter().zip(v) {
+        let eea = extended_euclidean_algorithm(&n, mi);
+        let l = eea.r.len() - 2;
+        let u = eea.r[l].lu().multiplicative_inverse().unwrap();
+        let g = eea.r[l].clone() * u.clone();
+        let s = eea.s[l].clone() * u;
+
+        let lcm = (n.clone() / g.clone() * mi.clone()).normal();
+        a = (a.clone() + s * n.clone() * ((vi.clone() - a) / g)).rem_canonical(&lcm);
+        n = lcm;
+        debug!("a = {a:?} mod {n:?}");
+    }
+
+    Ok((a, n))
+}
+
+/// Garner's algorithm for the CRA in mixed radix representation.
+///
+/// - Input: pairwise coprime nonzero `m₀, ..., mᵣ₋₁` and `v₀, ..., vᵣ₋₁`.
+/// - Output: the unique `a` with `a ≡ vᵢ mod mᵢ` and `d(a) < d(m)` for
+///   `m = m₀ ··· mᵣ₋₁`.
+///
+/// The mixed radix digits `d(cᵢ) < d(mᵢ)` of
+/// `a = c₀ + c₁ m₀ + c₂ m₀m₁ + ··· + cᵣ₋₁ m₀ ··· mᵣ₋₂` are computed one
+/// at a time from `cᵢ ≡ (vᵢ - (c₀ + ··· + cᵢ₋₁ m₀ ··· mᵢ₋₂)) (m₀ ··· mᵢ₋₁)⁻¹
+/// mod mᵢ`, so the result only grows incrementally and each step works
+/// modulo a single `mᵢ`.
+pub fn garners_algorithm<R: EuclideanDomain>(ms: &[R], v: &[R]) -> Result<R, CraError<R>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "Garner's algorithm",
+        ms = format!("{ms:?}"),
+        v = format!("{v:?}"),
+    );
+    let _enter = scope.enter();
+
+    check_lengths(ms, v)?;
+    if let Some(i) = ms.iter().position(|m| m.is_zero()) {
+        return Err(CraError::ZeroModulus { i });
+    }
+
+    let mut a = R::zero();
+    let mut radix = R::one();
     for (i, (mi, vi)) in ms.iter().zip(v).enumerate() {
-        let res = extended_euclidean_algorithm(&(m.clone() / mi.clone()), mi);
-        debug!("EEA on {:?} and {:?}", m.clone() / mi.clone(), mi);
-        println!("{}, {:?}", res, &res.s[res.s.len() - 2]);
-        let ci = (vi.clone() * res.s[res.s.len() - 2].clone()) % mi.clone();
-        debug!("c{i} = {ci:?}");
-        c.push(ci);
+        let eea = extended_euclidean_algorithm(&(radix.clone() % mi.clone()), mi);
+        let l = eea.r.len() - 2;
+        let Some(u) = eea.r[l].multiplicative_inverse() else {
+            let j = (0..i)
+                .find(|&j| !gcd(&ms[j], mi).is_one())
+                .expect("some earlier modulus shares a factor");
+            return Err(CraError::NotCoprime {
+                i: j,
+                j: i,
+                gcd: gcd(&ms[j], mi),
+            });
+        };
+        let radix_inv = eea.s[l].clone() * u;
+
+        let c = ((vi.clone() - a.clone()) * radix_inv).rem_canonical(mi);
+        debug!("c{i} = {c:?}");
+        a = a + c * radix.clone();
+        radix = radix * mi.clone();
+    }
+
+    Ok(a.rem_canonical(&radix))
+}
+
+/// Section 5.7 Rational function reconstruction.
+///
+/// - Input: `m ∈ F[x]` of degree `n > 0`, `g ∈ F[x]` with `deg g < n`, and
+///   `k ∈ {0, ..., n}`.
+/// - Output: `r/t` with `r ≡ tg mod m`, `deg r < k`, `deg t ≤ n - k` and
+///   `gcd(t, m) = 1`, if such a fraction exists.
+///
+/// The solution is read off the first row `j` of the extended Euclidean
+/// algorithm of `m` and `g` with `deg rⱼ < k` (Theorem 5.16).
+pub fn rational_function_reconstruction<F: Field>(
+    g: &Polynomial<F>,
+    m: &Polynomial<F>,
+    k: Natural,
+) -> Option<RationalFunction<F>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(5.7) Rational function reconstruction",
+        g = format!("{g:?}"),
+        m = format!("{m:?}"),
+        k = k.to_string(),
+    );
+    let _enter = scope.enter();
+
+    assert!(k <= m.deg(), "k = {k} must be at most deg m = {}", m.deg());
+
+    let g = g.clone() % m.clone();
+    let eea = extended_euclidean_algorithm(m, &g);
+    debug!("\n{eea}");
+
+    let j = (0..eea.r.len()).find(|&j| eea.r[j].is_zero() || eea.r[j].deg() < k)?;
+    let (r, t) = (eea.r[j].clone(), eea.t[j].clone());
+    debug!("j = {j}, r = {r:?}, t = {t:?}");
+
+    if gcd(&t, m).deg() > 0 {
+        debug!("gcd(t, m) != 1, so there is no solution");
+        return None;
     }
 
-    let mut res = c
+    Some(Fraction::new(r, t))
+}
+
+/// Section 5.9 Padé approximation.
+///
+/// Computes the `(k - 1, n - k)`-Padé approximant `r/t` of the power series
+/// `g`, that is `r ≡ tg mod xⁿ` with `deg r < k`, `deg t ≤ n - k` and
+/// `t(0) ≠ 0`.
+pub fn pade_approximation<F: Field>(
+    g: &Polynomial<F>,
+    n: Natural,
+    k: Natural,
+) -> Option<RationalFunction<F>> {
+    rational_function_reconstruction(&g.rem_pow(n), &Polynomial::one().times_x(n), k)
+}
+
+/// Section 5.8 Cauchy interpolation.
+///
+/// Finds `r/t` with `deg r < k`, `deg t ≤ n - k` and `r(uᵢ) = vᵢ t(uᵢ)`, where
+/// `t(uᵢ) ≠ 0`, for the `n` samples `(uᵢ, vᵢ)`.
+pub fn cauchy_interpolation<F: Field>(
+    samples: &[(F, F)],
+    k: Natural,
+) -> Option<RationalFunction<F>> {
+    let m = samples
         .iter()
-        .zip(ms)
-        .map(|(c_i, m_i)| c_i.clone() * (m.clone() / m_i.clone()))
-        .reduce(|a, b| a + b)
-        .unwrap()
-        % m.clone();
-    debug!("The result was {res:?}, but now find the smallest positive integer mod {m:?}");
-    while res < R::zero() {
-        res = (res + m.clone()) % m.clone();
-    }
-    res
+        .map(|(u, _)| Polynomial::x() - u)
+        .fold(Polynomial::one(), |a, b| a * b);
+    let g = NewtonInterpolation::run(samples).ok()?;
+
+    rational_function_reconstruction(&g, &m, k)
+}
+
+/// Section 5.10 Rational number reconstruction.
+///
+/// - Input: `m ∈ N` with `m > 1`, `g ∈ Z` and bounds `n, d ∈ N` with
+///   `2nd < m`.
+/// - Output: `r/t ∈ Q` with `r ≡ tg mod m`, `|r| ≤ n`, `0 < t ≤ d` and
+///   `gcd(t, m) = 1`, if such a fraction exists.
+///
+/// Choosing `n = d = ⌊√(m/2)⌋` recovers any fraction that is small enough
+/// from its image modulo `m`.
+pub fn rational_number_reconstruction(
+    g: Integer,
+    m: Integer,
+    n: Natural,
+    d: Natural,
+) -> Option<Rational> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(5.10) Rational number reconstruction",
+        g = g.to_string(),
+        m = m.to_string(),
+        n = n.to_string(),
+        d = d.to_string(),
+    );
+    let _enter = scope.enter();
+
+    assert!(m > 1, "m = {m} must be greater than 1");
+
+    let eea = extended_euclidean_algorithm(&m, &g.rem_euclid(m));
+    debug!("\n{eea}");
+
+    let j = (0..eea.r.len()).find(|&j| eea.r[j].unsigned_abs() <= n)?;
+    let (r, t) = (eea.r[j], eea.t[j]);
+    debug!("j = {j}, r = {r}, t = {t}");
+
+    if t == 0 || t.unsigned_abs() > d || gcd(&t, &m) != 1 {
+        return None;
+    }
+
+    Some(
+        Rational {
+            num: r * t.signum(),
+            denom: t.unsigned_abs(),
+        }
+        .normalized(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poly(cs: &[Rational]) -> Polynomial<Rational> {
+        Polynomial::new(cs.to_vec())
+    }
+
+    #[test]
+    fn modular_to_rational() {
+        // 2/3 mod 5, 7 and 11
+        let m = 5 * 7 * 11;
+        let g = chinese_remainder_algorithm(&[5, 7, 11], &[4, 3, 8]).unwrap();
+        let bound = ((m / 2) as f64).sqrt() as Natural;
+
+        assert_eq!(
+            rational_number_reconstruction(g, m, bound, bound),
+            Some(Rational { num: 2, denom: 3 })
+        );
+        assert_eq!(
+            rational_number_reconstruction(-g, m, bound, bound),
+            Some(Rational { num: -2, denom: 3 })
+        );
+    }
+
+    #[test]
+    fn cra() {
+        assert_eq!(chinese_remainder_algorithm(&[5, 7], &[1, 3]), Ok(31));
+        assert_eq!(chinese_remainder_algorithm(&[3, 4, 5], &[2, 3, -1]), Ok(59));
+        assert_eq!(chinese_remainder_algorithm::<Integer>(&[], &[]), Ok(0));
+        assert_eq!(
+            chinese_remainder_algorithm(&[4, 6], &[1, 1]),
+            Err(CraError::NotCoprime { i: 0, j: 1, gcd: 2 })
+        );
+        assert_eq!(
+            chinese_remainder_algorithm(&[4, 0], &[1, 1]),
+            Err(CraError::ZeroModulus { i: 1 })
+        );
+        assert_eq!(
+            chinese_remainder_algorithm(&[4, 5], &[1]),
+            Err(CraError::LengthMismatch {
+                moduli: 2,
+                values: 1
+            })
+        );
+
+        let ms = [5, 7, 11, 13];
+        let basis = CrtBasis::new(&ms).unwrap();
+        assert_eq!(*basis.modulus(), 5005);
+        for a in [0, 1, 1234, 5004] {
+            let v = ms.map(|m| a % m);
+            assert_eq!(basis.reconstruct(&v), Ok(a));
+            assert_eq!(garners_algorithm(&ms, &v), Ok(a));
+        }
+        assert_eq!(
+            garners_algorithm(&[3, 5, 9], &[1, 1, 1]),
+            Err(CraError::NotCoprime { i: 0, j: 2, gcd: 3 })
+        );
+    }
+
+    #[test]
+    fn generalized_cra() {
+        // x ≡ 3 mod 4, x ≡ 5 mod 6, x ≡ 2 mod 9
+        assert_eq!(
+            generalized_chinese_remainder_algorithm(&[4, 6, 9], &[3, 5, 2]),
+            Ok((11, 36))
+        );
+        assert_eq!(
+            generalized_chinese_remainder_algorithm(&[4, 6], &[1, 2]),
+            Err(CraError::Incompatible { i: 0, j: 1, gcd: 2 })
+        );
+        assert_eq!(
+            generalized_chinese_remainder_algorithm(&[5, 7], &[1, 3]),
+            Ok((31, 35))
+        );
+    }
+
+    #[test]
+    fn polynomial_cra() {
+        // f ≡ 1 mod x, f ≡ 2 mod x - 1 and f ≡ x mod x² + 1 over Q
+        let ms = [
+            poly(&[0, 1].map(Rational::from)),
+            poly(&[-1, 1].map(Rational::from)),
+            poly(&[1, 0, 1].map(Rational::from)),
+        ];
+        let v = [
+            poly(&[Rational::one()]),
+            poly(&[Rational::from(2)]),
+            poly(&[0, 1].map(Rational::from)),
+        ];
+
+        let f = chinese_remainder_algorithm(&ms, &v).unwrap();
+        assert!(f.deg() < 4);
+        for (m, v) in ms.iter().zip(&v) {
+            assert!(((f.clone() - v.clone()) % m.clone()).is_zero());
+        }
+        assert_eq!(garners_algorithm(&ms, &v), Ok(f.clone()));
+
+        let (g, l) = generalized_chinese_remainder_algorithm(
+            &[ms[0].clone() * ms[1].clone(), ms[1].clone() * ms[2].clone()],
+            &[
+                f.clone() % (ms[0].clone() * ms[1].clone()),
+                f.clone() % (ms[1].clone() * ms[2].clone()),
+            ],
+        )
+        .unwrap();
+        let m = ms.iter().fold(Polynomial::one(), |m, mi| m * mi.clone());
+        assert_eq!((g, l), (f, m));
+    }
+
+    #[test]
+    fn pade_of_exp() {
+        // exp(x) ≡ 1 + x + x²/2 + x³/6 mod x⁴
+        let g = poly(&[1, 1, 2, 6].map(|d| Rational { num: 1, denom: d }));
+        let f = pade_approximation(&g, 4, 2).unwrap();
+
+        // The (1, 2)-Padé approximant of exp(x) is (2x + 6)/(x² - 4x + 6)
+        assert_eq!(
+            f,
+            Fraction::new(
+                poly(&[6, 2].map(Rational::from)),
+                poly(&[6, -4, 1].map(Rational::from))
+            )
+        );
+        assert_eq!(
+            (f.denom.clone() * g).rem_pow(4),
+            f.num,
+            "r ≡ tg mod x⁴ for {f:?}"
+        );
+    }
+
+    #[test]
+    fn cauchy() {
+        // (x + 1)/(x - 5)
+        let samples = [0, 1, 2, 3].map(|u| {
+            (
+                Rational::from(u),
+                Rational::from(u + 1) / Rational::from(u - 5),
+            )
+        });
+        let f = cauchy_interpolation(&samples, 2).unwrap();
+
+        assert_eq!(
+            f,
+            Fraction::new(
+                poly(&[1, 1].map(Rational::from)),
+                poly(&[-5, 1].map(Rational::from))
+            )
+        );
+        for (u, v) in samples {
+            assert_eq!(f.evaluate_at(u), Some(v));
+        }
+    }
 }
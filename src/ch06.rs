@@ -0,0 +1,552 @@
+//! # Resultants and gcd computation
+
+use tracing::debug;
+
+use crate::{
+    ch03::gcd,
+    ch05::CrtBasis,
+    euclidean_domain::EuclideanDomain,
+    finite::{with_prime, PRIMES},
+    matrix::Matrix,
+    Finite, Group, Integer, Natural, Polynomial, Ring,
+};
+
+/// Definition 6.2 The Sylvester matrix of `f` and `g`.
+///
+/// For `f` of degree `n` and `g` of degree `m` this is the `(n + m) × (n + m)`
+/// matrix whose first `m` rows hold the coefficients of `x^(m-1)f, ..., xf, f`
+/// and whose last `n` rows hold those of `x^(n-1)g, ..., xg, g`.
+pub fn sylvester_matrix<R: Ring>(f: &Polynomial<R>, g: &Polynomial<R>) -> Matrix<R> {
+    let n = f.deg() as usize;
+    let m = g.deg() as usize;
+
+    Matrix::from_fn(n + m, n + m, |i, j| {
+        let (p, shift) = if i < m { (f, i) } else { (g, i - m) };
+        let deg = p.deg() as usize;
+        if j < shift || j > shift + deg {
+            R::zero()
+        } else {
+            p.coef_at((deg - (j - shift)) as _)
+        }
+    })
+}
+
+/// Definition 6.2 The resultant `res(f, g)` as the determinant of the
+/// [`sylvester_matrix`], by [`Matrix::fraction_free_determinant`].
+pub fn resultant<R>(f: &Polynomial<R>, g: &Polynomial<R>) -> R
+where
+    R: Ring + std::ops::Div<Output = R>,
+{
+    if f.is_zero() || g.is_zero() {
+        return R::zero();
+    }
+
+    sylvester_matrix(f, g).fraction_free_determinant()
+}
+
+/// The resultant `res(f, g)` over a field computed along the Euclidean
+/// algorithm, using that for `f = qg + r` with `n = deg f`, `m = deg g > 0`
+///
+/// ```text
+/// res(f, g) = (-1)^(nm) lc(g)^(n - deg r) res(g, r)
+/// ```
+///
+/// and `res(f, g) = g^n` for constant `g`.
+pub fn resultant_euclidean<F>(f: &Polynomial<F>, g: &Polynomial<F>) -> F
+where
+    F: Ring + std::ops::Div<Output = F>,
+{
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "Euclidean resultant",
+        f = format!("{f:?}"),
+        g = format!("{g:?}"),
+    );
+    let _enter = scope.enter();
+
+    let mut f = f.clone();
+    let mut g = g.clone();
+    let mut res = F::one();
+
+    loop {
+        if f.is_zero() || g.is_zero() {
+            return F::zero();
+        }
+
+        let n = f.deg();
+        let m = g.deg();
+        if m == 0 {
+            return res * g.lc().pow(n);
+        }
+
+        let r = f.clone() % g.clone();
+        if r.is_zero() {
+            return F::zero();
+        }
+
+        res = res * g.lc().pow(n - r.deg());
+        if n * m % 2 == 1 {
+            res = -res;
+        }
+        debug!("res(f, g) = {res:?} · res({g:?}, {r:?})");
+
+        f = g;
+        g = r;
+    }
+}
+
+/// The subresultant polynomial remainder sequence `r₀ = f, r₁ = g, r₂, ...`
+/// for `deg f ≥ deg g`.
+///
+/// Each `rᵢ₊₁` is the pseudo-remainder of `rᵢ₋₁` and `rᵢ` divided by a factor
+/// `βᵢ` which keeps the coefficients as small as the subresultants, so `R`
+/// only needs exact division, e.g. `Integer`. The last element is a
+/// (non-normalized) gcd of `f` and `g`, and is `± res(f, g)` when that is a
+/// nonzero constant.
+pub fn subresultant_prs<R>(f: &Polynomial<R>, g: &Polynomial<R>) -> Vec<Polynomial<R>>
+where
+    R: Ring + std::ops::Div<Output = R>,
+{
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "Subresultant PRS",
+        f = format!("{f:?}"),
+        g = format!("{g:?}"),
+    );
+    let _enter = scope.enter();
+
+    assert!(f.deg() >= g.deg(), "deg f must be at least deg g");
+
+    let mut rs = vec![f.clone(), g.clone()];
+    if g.is_zero() {
+        rs.pop();
+        return rs;
+    }
+
+    let mut d = f.deg() - g.deg();
+    let mut psi = -R::one();
+    let mut beta = if d % 2 == 1 { R::one() } else { -R::one() };
+
+    loop {
+        let (prev, cur) = (&rs[rs.len() - 2], &rs[rs.len() - 1]);
+        let (_, prem) = prev.pseudo_div_rem(cur);
+        if prem.is_zero() {
+            break;
+        }
+
+        let next = Polynomial::new(prem.iter().map(|(c, _)| c.clone() / beta.clone()).collect());
+        debug!("r{} = {next:?}", rs.len());
+
+        let gamma = cur.lc();
+        psi = if d == 0 {
+            psi
+        } else {
+            (-gamma.clone()).pow(d) / psi.pow(d - 1)
+        };
+        d = cur.deg() - next.deg();
+        beta = -gamma * psi.pow(d);
+
+        let done = next.deg() == 0;
+        rs.push(next);
+        if done {
+            break;
+        }
+    }
+
+    rs
+}
+
+/// The discriminant `disc(f) = (-1)^(n(n-1)/2) res(f, f') / lc(f)` of `f` of
+/// degree `n`, which vanishes exactly if `f` has a repeated root.
+pub fn discriminant<R>(f: &Polynomial<R>) -> R
+where
+    R: Ring + std::ops::Div<Output = R>,
+{
+    let n = f.deg();
+    let res = resultant(f, &f.diff()) / f.lc();
+
+    if (n * (n.saturating_sub(1)) / 2) % 2 == 1 {
+        -res
+    } else {
+        res
+    }
+}
+
+/// Steps 4 and 5 of the modular gcd algorithms: the lifts of `w ≡ bv`,
+/// `f* ≡ bf/w` and `g* ≡ bg/w` where `v` is the monic gcd of `f` and `g`
+/// modulo `P`.
+fn modular_gcd_image<const P: Natural>(
+    f: &Polynomial<Integer>,
+    g: &Polynomial<Integer>,
+    b: Integer,
+) -> [Polynomial<Integer>; 3] {
+    let f = Polynomial::<Finite<P>>::from(f.clone());
+    let g = Polynomial::<Finite<P>>::from(g.clone());
+    let b = Finite::from(b);
+
+    let w = gcd(&f, &g).scale(&b);
+    let f_star = f.scale(&b) / w.clone();
+    let g_star = g.scale(&b) / w.clone();
+
+    [w.into(), f_star.into(), g_star.into()]
+}
+
+/// Reduces the gcd of arbitrary `f, g ∈ Z[x]` to that of primitive `f` and
+/// `g` with `deg f ≥ deg g ≥ 1`, as required by the modular algorithms.
+fn integer_gcd_with(
+    f: &Polynomial<Integer>,
+    g: &Polynomial<Integer>,
+    primitive_gcd: impl Fn(&Polynomial<Integer>, &Polynomial<Integer>) -> Option<Polynomial<Integer>>,
+) -> Option<Polynomial<Integer>> {
+    let (f, g) = if f.deg() >= g.deg() { (f, g) } else { (g, f) };
+
+    if g.is_zero() {
+        return Some(f.scale(&f.lc().lu()));
+    }
+
+    let c = gcd(&f.content(), &g.content());
+    if g.deg() == 0 {
+        return Some(Polynomial::new(vec![c]));
+    }
+
+    primitive_gcd(&f.primitive_part(), &g.primitive_part()).map(|h| h.scale(&c))
+}
+
+/// The bound `⌈(n + 1)^(1/2) 2ⁿ a b⌉` of Algorithms 6.28 and 15.19, or
+/// `None` if it exceeds the range of [`Natural`].
+///
+/// When the square `(n + 1)(2ⁿ a b)²` does not fit, `⌈(n + 1)^(1/2)⌉ 2ⁿ a b`
+/// is used instead.
+pub(crate) fn coefficient_bound(n: Natural, a: Natural, b: Natural) -> Option<Natural> {
+    let ceil_sqrt = |x: Natural| {
+        let r = x.isqrt();
+        if r * r < x {
+            r + 1
+        } else {
+            r
+        }
+    };
+
+    let k = 1u128
+        .checked_shl(n.try_into().ok()?)?
+        .checked_mul(a)?
+        .checked_mul(b)?;
+    match k.checked_mul(k).and_then(|k2| k2.checked_mul(n + 1)) {
+        Some(square) => Some(ceil_sqrt(square)),
+        None => ceil_sqrt(n + 1).checked_mul(k),
+    }
+}
+
+/// The bound `B = ⌈(n + 1)^(1/2) 2ⁿ A b⌉` on the coefficients of a factor of
+/// `bf` from Algorithm 6.28, or `None` if it exceeds the range of [`Natural`].
+fn modular_gcd_bound(
+    f: &Polynomial<Integer>,
+    g: &Polynomial<Integer>,
+    b: Integer,
+) -> Option<Natural> {
+    let a = f.max_norm().max(g.max_norm());
+    coefficient_bound(f.deg(), a, b.unsigned_abs())
+}
+
+/// Algorithm 6.28 Big prime modular gcd.
+///
+/// - Input: `f, g ∈ Z[x]`.
+/// - Output: `gcd(f, g) ∈ Z[x]` with positive leading coefficient, or `None`
+///   if no prime of [`PRIMES`] exceeds `2B`.
+///
+/// The primes tried are those of [`PRIMES`] above `2B` not dividing
+/// `b = gcd(lc(f), lc(g))`, in increasing order.
+pub fn big_prime_modular_gcd(
+    f: &Polynomial<Integer>,
+    g: &Polynomial<Integer>,
+) -> Option<Polynomial<Integer>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(6.28) Big prime modular gcd",
+        f = format!("{f:?}"),
+        g = format!("{g:?}"),
+    );
+    let _enter = scope.enter();
+
+    integer_gcd_with(f, g, |f, g| {
+        // 1.
+        let b = gcd(&f.lc(), &g.lc());
+        let bound = modular_gcd_bound(f, g, b)?;
+        debug!("b = {b}, B = {bound}");
+
+        // 2.
+        for p in PRIMES
+            .into_iter()
+            .filter(|p| *p / 2 > bound && b % *p as Integer != 0)
+        {
+            // 3. 4. 5.
+            let [w, f_star, g_star] = with_prime!(p, P => modular_gcd_image::<P>(f, g, b));
+            debug!("p = {p}: w = {w:?}, f* = {f_star:?}, g* = {g_star:?}");
+
+            // 6.
+            if f_star.one_norm().saturating_mul(w.one_norm()) <= bound
+                && g_star.one_norm().saturating_mul(w.one_norm()) <= bound
+            {
+                // 7.
+                return Some(w.primitive_part());
+            }
+        }
+
+        debug!("B = {bound} is too large for the available primes");
+        None
+    })
+}
+
+/// Algorithm 6.38 Small primes modular gcd.
+///
+/// - Input: `f, g ∈ Z[x]`.
+/// - Output: `gcd(f, g) ∈ Z[x]` with positive leading coefficient, or `None`
+///   if the product `m` of the lucky primes cannot exceed `2B` while the
+///   reconstruction modulo `m` stays within the range of [`Integer`].
+///
+/// The gcd is computed modulo the primes of [`PRIMES`] not dividing
+/// `b = gcd(lc(f), lc(g))`, discarding unlucky primes where the degree of the
+/// modular gcd is too large, until the product of the lucky ones exceeds
+/// `2B`. The images are then combined with a [`CrtBasis`] for the chosen
+/// primes.
+pub fn small_primes_modular_gcd(
+    f: &Polynomial<Integer>,
+    g: &Polynomial<Integer>,
+) -> Option<Polynomial<Integer>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(6.38) Small primes modular gcd",
+        f = format!("{f:?}"),
+        g = format!("{g:?}"),
+    );
+    let _enter = scope.enter();
+
+    integer_gcd_with(f, g, |f, g| {
+        // 1.
+        let b = gcd(&f.lc(), &g.lc());
+        let bound = modular_gcd_bound(f, g, b)?;
+        debug!("b = {b}, B = {bound}");
+
+        let mut primes: Vec<Integer> = vec![];
+        let mut images: Vec<[Polynomial<Integer>; 3]> = vec![];
+
+        for p in PRIMES.into_iter().filter(|p| b % *p as Integer != 0) {
+            // 2. 3.
+            let image = with_prime!(p, P => modular_gcd_image::<P>(f, g, b));
+            let e = image[0].deg();
+            debug!("p = {p}: w = {:?} of degree {e}", image[0]);
+
+            // 4.
+            match images.first().map(|i| i[0].deg()) {
+                Some(min) if e > min => {
+                    debug!("p = {p} is unlucky");
+                    continue;
+                }
+                Some(min) if e < min => {
+                    debug!("the primes {primes:?} were all unlucky");
+                    primes.clear();
+                    images.clear();
+                }
+                _ => {}
+            }
+            primes.push(p as _);
+            images.push(image);
+
+            let Some(m) = primes
+                .iter()
+                .try_fold(1 as Integer, |m, p| m.checked_mul(*p))
+            else {
+                debug!("the product of the primes {primes:?} exceeds the range of Integer");
+                return None;
+            };
+            if m.unsigned_abs() / 2 <= bound {
+                continue;
+            }
+            // the sums Σ vᵢ eᵢ with vᵢ < pᵢ and eᵢ < m in the reconstruction
+            let largest = *primes.iter().max().unwrap();
+            if m.checked_mul(largest)
+                .and_then(|s| s.checked_mul(primes.len() as Integer))
+                .is_none()
+            {
+                debug!("the reconstruction modulo m = {m} exceeds the range of Integer");
+                return None;
+            }
+
+            // 5.
+            let basis = CrtBasis::new(&primes).expect("distinct primes are coprime");
+            let [w, f_star, g_star] = std::array::from_fn(|i| {
+                let deg = images.iter().map(|image| image[i].deg()).max().unwrap();
+                Polynomial::new(
+                    (0..=deg)
+                        .map(|j| {
+                            let v: Vec<_> =
+                                images.iter().map(|image| image[i].coef_at(j)).collect();
+                            let c = basis.reconstruct(&v).unwrap();
+                            if c > m / 2 {
+                                c - m
+                            } else {
+                                c
+                            }
+                        })
+                        .collect(),
+                )
+            });
+            debug!("m = {m}: w = {w:?}, f* = {f_star:?}, g* = {g_star:?}");
+
+            // 6.
+            if f_star.one_norm().saturating_mul(w.one_norm()) <= bound
+                && g_star.one_norm().saturating_mul(w.one_norm()) <= bound
+            {
+                return Some(w.primitive_part());
+            }
+        }
+
+        None
+    })
+}
+
+/// The gcd of `f, g ∈ Z[x]` with positive leading coefficient.
+///
+/// This is [`small_primes_modular_gcd`] unless the coefficients are too large
+/// for it, in which case the primitive part of the last element of the
+/// [`subresultant_prs`] is used.
+pub fn integer_gcd(f: &Polynomial<Integer>, g: &Polynomial<Integer>) -> Polynomial<Integer> {
+    small_primes_modular_gcd(f, g).unwrap_or_else(|| {
+        integer_gcd_with(f, g, |f, g| {
+            subresultant_prs(f, g).pop().map(|r| r.primitive_part())
+        })
+        .expect("the subresultant PRS is nonempty")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Finite, Integer, Rational};
+
+    fn int_poly(cs: &[i128]) -> Polynomial<Integer> {
+        Polynomial::new(cs.to_vec())
+    }
+
+    #[test]
+    fn resultant_agrees_with_euclidean() {
+        let f = [2, -3, 0, 1, 5];
+        let g = [-1, 4, 2];
+
+        let to_rat = |cs: &[i128]| Polynomial::new(cs.iter().map(|&c| Rational::from(c)).collect());
+
+        let res = resultant(&int_poly(&f), &int_poly(&g));
+        assert_eq!(Rational::from(res), resultant(&to_rat(&f), &to_rat(&g)));
+        assert_eq!(
+            Rational::from(res),
+            resultant_euclidean(&to_rat(&f), &to_rat(&g))
+        );
+        assert_eq!(
+            Rational::from(res),
+            resultant_euclidean(&to_rat(&g), &to_rat(&f))
+        );
+
+        let to_fin =
+            |cs: &[i128]| Polynomial::new(cs.iter().map(|&c| Finite::<7>::from(c)).collect());
+        assert_eq!(
+            Finite::<7>::from(res),
+            resultant_euclidean(&to_fin(&f), &to_fin(&g))
+        );
+    }
+
+    #[test]
+    fn common_root() {
+        // (x - 2)(x + 1) and (x - 2)(x + 3)
+        assert_eq!(
+            resultant(&int_poly(&[-2, -1, 1]), &int_poly(&[-6, 1, 1])),
+            0
+        );
+    }
+
+    #[test]
+    fn subresultants() {
+        // The classical example from Knuth, TAOCP vol. 2, Section 4.6.1
+        let f = int_poly(&[-5, 2, 8, -3, -3, 0, 1, 0, 1]);
+        let g = int_poly(&[21, -9, -4, 0, 5, 0, 3]);
+
+        let prs = subresultant_prs(&f, &g);
+
+        assert_eq!(
+            prs[2..],
+            [
+                int_poly(&[9, 0, -3, 0, 15]),
+                int_poly(&[-245, 125, 65]),
+                int_poly(&[-12300, 9326]),
+                int_poly(&[260708]),
+            ]
+        );
+        assert_eq!(resultant(&f, &g), 260708);
+    }
+
+    #[test]
+    fn modular_gcds() {
+        // 6(x + 1)(x - 2)(2x + 3) and 4(x + 1)(x - 2)(x² + 5)
+        let h = int_poly(&[-2, -1, 1]);
+        let f = h.clone() * int_poly(&[3, 2]).scale(&6);
+        let g = h.clone() * int_poly(&[5, 0, 1]).scale(&-4);
+
+        assert_eq!(big_prime_modular_gcd(&f, &g), Some(h.scale(&2)));
+        assert_eq!(small_primes_modular_gcd(&f, &g), Some(h.scale(&2)));
+        assert_eq!(small_primes_modular_gcd(&g, &f), Some(h.scale(&2)));
+        assert_eq!(integer_gcd(&f, &g), h.scale(&2));
+
+        assert_eq!(
+            big_prime_modular_gcd(&f, &int_poly(&[4])),
+            Some(int_poly(&[2]))
+        );
+        assert_eq!(
+            small_primes_modular_gcd(&f.scale(&-1), &int_poly(&[])),
+            Some(f)
+        );
+    }
+
+    #[test]
+    fn modular_gcds_with_large_coefficients() {
+        // (x + 1)(x³ + 2⁵⁸) and (x + 1)(x² + 2⁵⁷ + 1), where 2B > 2⁶² exceeds
+        // every prime of the table but not their products
+        let h = int_poly(&[1, 1]);
+        let f = h.clone() * int_poly(&[1 << 58, 0, 0, 1]);
+        let g = h.clone() * int_poly(&[(1 << 57) + 1, 0, 1]);
+
+        assert_eq!(big_prime_modular_gcd(&f, &g), None);
+        assert_eq!(small_primes_modular_gcd(&f, &g), Some(h.clone()));
+        assert_eq!(integer_gcd(&f, &g), h);
+
+        // B ≥ 2¹⁰⁰ 2³⁰ is out of reach for both
+        let mut cs = vec![0; 101];
+        (cs[0], cs[100]) = (1 << 30, 1);
+        let f = h.clone() * int_poly(&cs);
+        let g = h.clone() * int_poly(&[0, 1]);
+
+        assert_eq!(big_prime_modular_gcd(&f, &g), None);
+        assert_eq!(small_primes_modular_gcd(&f, &g), None);
+        assert_eq!(integer_gcd(&f, &g), h);
+
+        // m > 2B fits, but the sums in the reconstruction modulo m do not
+        let f = h.clone() * int_poly(&[(1 << 115) + 1, 1]);
+        let g = h.clone() * int_poly(&[(1 << 115) + 7, 1]);
+
+        assert_eq!(small_primes_modular_gcd(&f, &g), None);
+        assert_eq!(integer_gcd(&f, &g), h);
+    }
+
+    #[test]
+    fn content_and_primitive_part() {
+        let f = int_poly(&[6, -9, 0, -3]);
+
+        assert_eq!(f.content(), -3);
+        assert_eq!(f.primitive_part(), int_poly(&[-2, 3, 0, 1]));
+    }
+
+    #[test]
+    fn quadratic_discriminant() {
+        // b² - 4ac
+        assert_eq!(discriminant(&int_poly(&[3, 5, 2])), 25 - 4 * 2 * 3);
+        assert_eq!(discriminant(&int_poly(&[1, 2, 1])), 0);
+    }
+}
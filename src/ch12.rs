@@ -0,0 +1,140 @@
+//! # Fast linear algebra
+
+use tracing::debug;
+
+use crate::{matrix::Matrix, Ring};
+
+/// The `rows × cols` matrix with `a` in its upper left corner and zeros
+/// elsewhere.
+fn pad<R: Ring>(a: &Matrix<R>, rows: usize, cols: usize) -> Matrix<R> {
+    Matrix::from_fn(rows, cols, |i, j| {
+        if i < a.rows() && j < a.cols() {
+            a[(i, j)].clone()
+        } else {
+            R::zero()
+        }
+    })
+}
+
+/// The four `rows/2 × cols/2` blocks of `a`, for even dimensions.
+fn blocks<R: Ring>(a: &Matrix<R>) -> [Matrix<R>; 4] {
+    let (m, n) = (a.rows() / 2, a.cols() / 2);
+    [
+        a.submatrix(0..m, 0..n),
+        a.submatrix(0..m, n..2 * n),
+        a.submatrix(m..2 * m, 0..n),
+        a.submatrix(m..2 * m, n..2 * n),
+    ]
+}
+
+/// Section 12.1 Strassen's matrix multiplication.
+///
+/// - Input: `A ∈ R^(m×k)`, `B ∈ R^(k×n)` and a `threshold ≥ 1`.
+/// - Output: `AB ∈ R^(m×n)`.
+///
+/// Odd dimensions are padded with zeros and the blocks
+/// `A = (A₁₁ A₁₂; A₂₁ A₂₂)`, `B = (B₁₁ B₁₂; B₂₁ B₂₂)` are multiplied with
+/// seven recursive products instead of eight, down to matrices whose
+/// dimensions are at most `threshold`, where the classical method takes
+/// over. For `n × n` matrices with `n` a power of two and `threshold = 1`
+/// this uses `n^(log₂ 7) ≈ n^2.81` multiplications in `R` instead of `n³`.
+pub fn strassens_algorithm<R: Ring>(a: &Matrix<R>, b: &Matrix<R>, threshold: usize) -> Matrix<R> {
+    assert_eq!(a.cols(), b.rows(), "the dimensions do not match");
+    assert!(threshold >= 1, "the threshold must be positive");
+
+    let (m, k, n) = (a.rows(), a.cols(), b.cols());
+    if m.max(k).max(n) <= threshold {
+        return a * b;
+    }
+
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(12.1) Strassen",
+        m = m.to_string(),
+        k = k.to_string(),
+        n = n.to_string(),
+    );
+    let _enter = scope.enter();
+
+    let (m2, k2, n2) = (m.div_ceil(2) * 2, k.div_ceil(2) * 2, n.div_ceil(2) * 2);
+    let [a11, a12, a21, a22] = blocks(&pad(a, m2, k2));
+    let [b11, b12, b21, b22] = blocks(&pad(b, k2, n2));
+
+    let mul = |x: Matrix<R>, y: Matrix<R>| strassens_algorithm(&x, &y, threshold);
+    let p1 = mul(a11.clone() + a22.clone(), b11.clone() + b22.clone());
+    let p2 = mul(a21.clone() + a22.clone(), b11.clone());
+    let p3 = mul(a11.clone(), b12.clone() - b22.clone());
+    let p4 = mul(a22.clone(), b21.clone() - b11.clone());
+    let p5 = mul(a11.clone() + a12.clone(), b22.clone());
+    let p6 = mul(a21 - a11, b11 + b12);
+    let p7 = mul(a12 - a22, b21 + b22);
+
+    let c11 = p1.clone() + p4.clone() - p5.clone() + p7;
+    let c12 = p3.clone() + p5;
+    let c21 = p2.clone() + p4;
+    let c22 = p1 - p2 + p3 + p6;
+    debug!("C₁₁ = {c11:?}, C₁₂ = {c12:?}, C₂₁ = {c21:?}, C₂₂ = {c22:?}");
+
+    let (h, w) = (m2 / 2, n2 / 2);
+    Matrix::from_fn(m, n, |i, j| {
+        match (i < h, j < w) {
+            (true, true) => &c11[(i, j)],
+            (true, false) => &c12[(i, j - w)],
+            (false, true) => &c21[(i - h, j)],
+            (false, false) => &c22[(i - h, j - w)],
+        }
+        .clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        count_ops::{self, CountOps},
+        Integer,
+    };
+
+    use super::*;
+
+    fn matrix(rows: usize, cols: usize, seed: Integer) -> Matrix<CountOps<Integer>> {
+        Matrix::from_fn(rows, cols, |i, j| {
+            CountOps::from((seed * (i as Integer + 1) + 3 * j as Integer) % 11 - 5)
+        })
+    }
+
+    #[test]
+    fn rectangular() {
+        for (m, k, n) in [(5, 7, 3), (1, 4, 1), (6, 6, 6), (9, 2, 4)] {
+            let a = matrix(m, k, 3);
+            let b = matrix(k, n, 7);
+            for threshold in [1, 2, 4] {
+                assert_eq!(strassens_algorithm(&a, &b, threshold), &a * &b);
+            }
+        }
+    }
+
+    #[test]
+    fn multiplication_counts() {
+        let n = 16;
+        let a = matrix(n, n, 2);
+        let b = matrix(n, n, 5);
+
+        count_ops::reset();
+        let classical = &a * &b;
+        let classical_counts = count_ops::get_counts();
+        assert_eq!(classical_counts.multiplications, 16 * 16 * 16);
+
+        count_ops::reset();
+        let strassen = strassens_algorithm(&a, &b, 1);
+        let strassen_counts = count_ops::get_counts();
+        assert_eq!(strassen, classical);
+        assert_eq!(strassen_counts.multiplications, 7 * 7 * 7 * 7);
+
+        // two levels of recursion, then classical 4 × 4 products
+        count_ops::reset();
+        assert_eq!(strassens_algorithm(&a, &b, 4), classical);
+        assert_eq!(count_ops::get_counts().multiplications, 7 * 7 * 64);
+
+        assert!(strassen_counts.additions > classical_counts.additions);
+    }
+}
@@ -0,0 +1,821 @@
+//! # Factoring polynomials over finite fields
+
+use itertools::Itertools;
+use rand::Rng;
+use tracing::debug;
+
+use crate::{
+    ch03::gcd,
+    field::Field,
+    matrix::Matrix,
+    number_theory::{mobius, prime_divisors},
+    Finite, Group, Integer, Natural, Polynomial, Ring,
+};
+
+/// Algorithm 14.21 Yun's squarefree factorization.
+///
+/// - Input: monic `f ∈ F[x]` of degree `n > 0`, where `F` has characteristic
+///   zero or greater than `n`.
+/// - Output: the squarefree decomposition `(a₁, ..., aₘ)` of `f`, that is
+///   `f = a₁ a₂² ··· aₘᵐ` with `a₁, ..., aₘ` monic, squarefree and pairwise
+///   coprime and `aₘ ≠ 1`.
+///
+/// The result is indexed from zero, so `result[i]` is `aᵢ₊₁`.
+pub fn yuns_squarefree_factorization<F: Field>(f: &Polynomial<F>) -> Vec<Polynomial<F>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(14.21) Yun's squarefree factorization",
+        f = format!("{f:?}"),
+    );
+    let _enter = scope.enter();
+
+    assert!(
+        f.deg() > 0 && f.lc().is_one(),
+        "Yun's algorithm needs a monic f of positive degree"
+    );
+
+    // 1.
+    let u = gcd(f, &f.diff());
+    let mut v = f.clone() / u.clone();
+    let mut w = f.diff() / u;
+    debug!("v1 = {v:?}, w1 = {w:?}");
+
+    // 2.
+    let mut a = vec![];
+    loop {
+        // 3.
+        let h = gcd(&v, &(w.clone() - v.diff()));
+        let next_w = (w - v.diff()) / h.clone();
+        v = v / h.clone();
+        w = next_w;
+        debug!("a{} = {h:?}, v = {v:?}, w = {w:?}", a.len() + 1);
+        a.push(h);
+
+        // 4.
+        if v.is_one() {
+            break;
+        }
+    }
+
+    a
+}
+
+/// The `p`-th root `g` of `f = g^p ∈ F_p[x]`, which requires `f' = 0`.
+fn pth_root<const P: Natural>(f: &Polynomial<Finite<P>>) -> Polynomial<Finite<P>> {
+    // Frobenius is the identity on F_p, so g(x)^p = g(x^p)
+    Polynomial::new(
+        f.iter()
+            .filter(|(_, i)| i % P == 0)
+            .map(|(c, _)| *c)
+            .collect(),
+    )
+}
+
+/// Squarefree factorization over the finite field `F_p`.
+///
+/// Yun's algorithm breaks down in positive characteristic since `f' = 0` for
+/// `f = g(x^p)`. As in Section 14.6, the factors whose multiplicity is not
+/// divisible by `p` are split off with gcds as in Yun's algorithm, and the
+/// remaining factor `c = g^p` is handled by recursing on its `p`-th root.
+///
+/// - Input: monic `f ∈ F_p[x]` of degree `n > 0`.
+/// - Output: `(a₁, ..., aₘ)` with `f = a₁ a₂² ··· aₘᵐ` as in
+///   [`yuns_squarefree_factorization`].
+pub fn squarefree_factorization_over_finite_field<const P: Natural>(
+    f: &Polynomial<Finite<P>>,
+) -> Vec<Polynomial<Finite<P>>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "Squarefree factorization over F_p",
+        f = format!("{f:?}"),
+        p = P.to_string(),
+    );
+    let _enter = scope.enter();
+
+    fn set<const P: Natural>(
+        a: &mut Vec<Polynomial<Finite<P>>>,
+        i: usize,
+        h: Polynomial<Finite<P>>,
+    ) {
+        if a.len() < i {
+            a.resize(i, Polynomial::one());
+        }
+        a[i - 1] = a[i - 1].clone() * h;
+    }
+
+    let mut a = vec![];
+
+    let df = f.diff();
+    let mut c = if df.is_zero() {
+        f.clone()
+    } else {
+        let mut c = gcd(f, &df);
+        let mut w = f.clone() / c.clone();
+        let mut i = 1;
+        while !w.is_one() {
+            let y = gcd(&w, &c);
+            let h = w / y.clone();
+            debug!("a{i} = {h:?}");
+            set(&mut a, i, h);
+            i += 1;
+            w = y.clone();
+            c = c / y;
+        }
+        c
+    };
+
+    if !c.is_one() {
+        c = pth_root(&c);
+        debug!("recursing on the p-th root {c:?}");
+        for (i, h) in squarefree_factorization_over_finite_field(&c)
+            .into_iter()
+            .enumerate()
+        {
+            set(&mut a, (i + 1) * P as usize, h);
+        }
+    }
+
+    while a.last().is_some_and(|h| h.is_one()) {
+        a.pop();
+    }
+
+    a
+}
+
+/// A uniformly random polynomial of degree less than `n`.
+fn random_polynomial<const P: Natural>(n: Natural, rng: &mut impl Rng) -> Polynomial<Finite<P>> {
+    Polynomial::new((0..n).map(|_| Finite::from(rng.gen_range(0..P))).collect())
+}
+
+/// Algorithm 14.3 Distinct-degree factorization.
+///
+/// - Input: squarefree monic `f ∈ F_p[x]` of degree `n > 0`.
+/// - Output: the distinct-degree decomposition `(g₁, ..., gₛ)` of `f`, where
+///   `gᵢ` is the product of all monic irreducible factors of `f` of degree
+///   `i` and `gₛ ≠ 1`.
+///
+/// The result is indexed from zero, so `result[i]` is `gᵢ₊₁`.
+pub fn distinct_degree_factorization<const P: Natural>(
+    f: &Polynomial<Finite<P>>,
+) -> Vec<Polynomial<Finite<P>>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(14.3) Distinct-degree factorization",
+        f = format!("{f:?}"),
+        p = P.to_string(),
+    );
+    let _enter = scope.enter();
+
+    // 1.
+    let x = Polynomial::x();
+    let mut h = x.clone();
+    let mut rest = f.clone();
+    let mut g = vec![];
+
+    // 2.
+    while !rest.is_one() {
+        let i = g.len() as Natural + 1;
+
+        // all factors of the rest have degree at least i, so it is
+        // irreducible once its degree drops below 2i
+        if rest.deg() < 2 * i {
+            debug!("f{} = {rest:?} is irreducible", i - 1);
+            g.resize(rest.deg() as usize - 1, Polynomial::one());
+            g.push(rest);
+            break;
+        }
+
+        // 3.
+        h = h.pow_mod(P, f);
+        let gi = gcd(&(h.clone() - x.clone()), &rest);
+        rest = rest / gi.clone();
+        debug!("h{i} = {h:?}, g{i} = {gi:?}, f{i} = {rest:?}");
+        g.push(gi);
+    }
+
+    g
+}
+
+/// Algorithm 14.8 Equal-degree splitting.
+///
+/// - Input: squarefree monic `f ∈ F_p[x]` of degree `n > 0`, all of whose
+///   irreducible factors have degree `d`.
+/// - Output: a proper monic factor of `f`, or `None` for failure, which
+///   happens with probability less than `1/2` when `f` is reducible.
+///
+/// For odd `p` the random `a` is split through `a^((pᵈ - 1)/2)`, for `p = 2`
+/// through the trace `a + a² + ··· + a^(2ᵈ⁻¹)`.
+pub fn equal_degree_splitting<const P: Natural>(
+    f: &Polynomial<Finite<P>>,
+    d: Natural,
+    rng: &mut impl Rng,
+) -> Option<Polynomial<Finite<P>>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(14.8) Equal-degree splitting",
+        f = format!("{f:?}"),
+        d = d.to_string(),
+    );
+    let _enter = scope.enter();
+
+    // 1.
+    let a = random_polynomial(f.deg(), rng);
+    debug!("a = {a:?}");
+    if a.deg() == 0 {
+        return None;
+    }
+
+    // 2.
+    let g1 = gcd(&a, f);
+    if !g1.is_one() {
+        debug!("gcd(a, f) = {g1:?}");
+        return Some(g1);
+    }
+
+    // 3.
+    let b = if P == 2 {
+        let mut power = a.clone();
+        let mut b = a;
+        for _ in 1..d {
+            power = power.pow_mod(2, f);
+            b = b + power.clone();
+        }
+        b
+    } else {
+        // (pᵈ - 1)/2 = (p - 1)/2 · (1 + p + ··· + pᵈ⁻¹), so b is the product
+        // of the Frobenius images of c = a^((p - 1)/2), which avoids the
+        // potentially huge exponent
+        let c = a.pow_mod((P - 1) / 2, f);
+        let mut power = c.clone();
+        let mut b = c;
+        for _ in 1..d {
+            power = power.pow_mod(P, f);
+            b = (b * power.clone()) % f.clone();
+        }
+        b - Finite::one()
+    };
+    debug!("b = {b:?}");
+
+    // 4.
+    let g2 = gcd(&b, f);
+    debug!("gcd(b, f) = {g2:?}");
+    if g2.is_one() || g2.deg() == f.deg() {
+        None
+    } else {
+        Some(g2)
+    }
+}
+
+/// Algorithm 14.10 Equal-degree factorization.
+///
+/// - Input: squarefree monic `f ∈ F_p[x]` of degree `n > 0`, all of whose
+///   irreducible factors have degree `d`.
+/// - Output: the monic irreducible factors of `f`.
+pub fn equal_degree_factorization<const P: Natural>(
+    f: &Polynomial<Finite<P>>,
+    d: Natural,
+    rng: &mut impl Rng,
+) -> Vec<Polynomial<Finite<P>>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(14.10) Equal-degree factorization",
+        f = format!("{f:?}"),
+        d = d.to_string(),
+    );
+    let _enter = scope.enter();
+
+    // 1.
+    if f.deg() == d {
+        return vec![f.clone()];
+    }
+
+    // 2.
+    let g = loop {
+        if let Some(g) = equal_degree_splitting(f, d, rng) {
+            break g;
+        }
+    };
+    debug!("f = ({g:?})({:?})", f.clone() / g.clone());
+
+    // 3.
+    let mut factors = equal_degree_factorization(&g, d, rng);
+    factors.extend(equal_degree_factorization(&(f.clone() / g), d, rng));
+    factors
+}
+
+/// Berlekamp's algorithm (Section 14.8).
+///
+/// - Input: squarefree monic `f ∈ F_p[x]` of degree `n > 0`.
+/// - Output: the monic irreducible factors of `f`.
+///
+/// The Berlekamp subalgebra `B = {a rem f : a^p ≡ a mod f}` is the left kernel
+/// of `Q - I`, where the `j`-th row of the Petr–Berlekamp matrix `Q` holds the
+/// coefficients of `x^(pj) rem f`. Its dimension is the number `r` of
+/// irreducible factors of `f`, and random elements of `B` split the factors
+/// found so far until there are `r` of them.
+pub fn berlekamps_algorithm<const P: Natural>(
+    f: &Polynomial<Finite<P>>,
+    rng: &mut impl Rng,
+) -> Vec<Polynomial<Finite<P>>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "Berlekamp's algorithm",
+        f = format!("{f:?}"),
+        p = P.to_string(),
+    );
+    let _enter = scope.enter();
+
+    // 1.
+    let n = f.deg();
+    let xp = Polynomial::x().pow_mod(P, f);
+    let mut q = vec![];
+    let mut row = Polynomial::one();
+    for j in 0..n {
+        q.push(
+            (0..n)
+                .map(|k| {
+                    row.coef_at(k)
+                        - if j == k {
+                            Finite::one()
+                        } else {
+                            Finite::zero()
+                        }
+                })
+                .collect_vec(),
+        );
+        row = (row * xp.clone()) % f.clone();
+    }
+
+    // 2.
+    let basis = Matrix::new(q)
+        .transpose()
+        .kernel()
+        .into_iter()
+        .map(Polynomial::new)
+        .collect_vec();
+    let r = basis.len();
+    debug!(
+        "r = {r}, B = ⟨{}⟩",
+        basis.iter().map(|b| format!("{b:?}")).join(", ")
+    );
+
+    // 3.
+    let mut factors = vec![f.clone()];
+    while factors.len() < r {
+        let a = basis.iter().fold(Polynomial::zero(), |a, b| {
+            a + b.scale(&Finite::from(rng.gen_range(0..P)))
+        });
+        debug!("a = {a:?}");
+
+        factors = factors
+            .into_iter()
+            .flat_map(|h| {
+                let b = if P == 2 {
+                    a.clone() % h.clone()
+                } else {
+                    a.pow_mod((P - 1) / 2, &h) - Finite::one()
+                };
+                let g = gcd(&b, &h);
+                if g.is_one() || g.deg() == h.deg() {
+                    vec![h]
+                } else {
+                    debug!("{h:?} = ({g:?})({:?})", h.clone() / g.clone());
+                    vec![h / g.clone(), g]
+                }
+            })
+            .collect();
+    }
+
+    factors
+}
+
+/// A factorization `unit · g₁^e₁ ··· gᵣ^eᵣ` with pairwise distinct
+/// irreducible `gᵢ`, which are monic over fields and primitive with positive
+/// leading coefficients over `Z`.
+#[derive(Clone)]
+pub struct Factorization<F> {
+    pub unit: F,
+    pub factors: Vec<(Polynomial<F>, Natural)>,
+}
+
+impl<F: Ring + std::fmt::Debug> Factorization<F> {
+    /// Multiplies the factorization back out.
+    pub fn expand(&self) -> Polynomial<F> {
+        self.factors
+            .iter()
+            .fold(Polynomial::new(vec![self.unit.clone()]), |acc, (g, e)| {
+                acc * g.pow(*e)
+            })
+    }
+}
+
+impl<F: Ring + std::fmt::Debug> std::fmt::Debug for Factorization<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.unit)?;
+        for (g, e) in &self.factors {
+            match e {
+                1 => write!(f, " · ({g:?})")?,
+                _ => write!(f, " · ({g:?})^{e}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How [`factorization_over_finite_field`] splits the squarefree parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplittingAlgorithm {
+    /// Distinct-degree factorization followed by Cantor–Zassenhaus
+    /// equal-degree factorization.
+    CantorZassenhaus,
+    /// [`berlekamps_algorithm`].
+    Berlekamp,
+}
+
+/// Polynomial factorization over finite fields (Section 14.4).
+///
+/// - Input: nonzero `f ∈ F_p[x]`.
+/// - Output: the factorization of `f` into its leading coefficient and
+///   monic irreducible factors with multiplicities, ordered by degree.
+///
+/// `f` is made monic and split into its squarefree parts, each of which is
+/// then factored with the given `algorithm`.
+pub fn factorization_over_finite_field<const P: Natural>(
+    f: &Polynomial<Finite<P>>,
+    algorithm: SplittingAlgorithm,
+    rng: &mut impl Rng,
+) -> Factorization<Finite<P>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "Polynomial factorization over F_p",
+        f = format!("{f:?}"),
+        p = P.to_string(),
+        algorithm = format!("{algorithm:?}"),
+    );
+    let _enter = scope.enter();
+
+    assert!(!f.is_zero(), "cannot factor zero");
+
+    let unit = f.lc();
+    let mut factors = vec![];
+    if f.deg() > 0 {
+        let monic = f.scale(&unit.multiplicative_inverse().unwrap());
+        for (i, a) in squarefree_factorization_over_finite_field(&monic)
+            .into_iter()
+            .enumerate()
+        {
+            if a.is_one() {
+                continue;
+            }
+
+            let irreducibles = match algorithm {
+                SplittingAlgorithm::CantorZassenhaus => distinct_degree_factorization(&a)
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, g)| !g.is_one())
+                    .flat_map(|(d, g)| equal_degree_factorization(&g, d as Natural + 1, rng))
+                    .collect_vec(),
+                SplittingAlgorithm::Berlekamp => berlekamps_algorithm(&a, rng),
+            };
+            debug!(
+                "a{} = {}",
+                i + 1,
+                irreducibles.iter().map(|g| format!("({g:?})")).join("")
+            );
+            factors.extend(irreducibles.into_iter().map(|g| (g, i as Natural + 1)));
+        }
+    }
+    factors.sort_by_key(|(g, _)| (g.deg(), g.iter().map(|(c, _)| *c).collect_vec()));
+
+    Factorization { unit, factors }
+}
+
+/// Root finding over finite fields (Section 14.5).
+///
+/// - Input: nonzero `f ∈ F_p[x]`.
+/// - Output: the distinct roots of `f` in `F_p`, in increasing order.
+///
+/// The product `g = gcd(x^p - x, f)` of the distinct linear factors of `f` is
+/// split with [`equal_degree_factorization`] for `d = 1`.
+pub fn roots_over_finite_field<const P: Natural>(
+    f: &Polynomial<Finite<P>>,
+    rng: &mut impl Rng,
+) -> Vec<Finite<P>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(14.5) Root finding over F_p",
+        f = format!("{f:?}"),
+        p = P.to_string(),
+    );
+    let _enter = scope.enter();
+
+    assert!(!f.is_zero(), "every element is a root of zero");
+    if f.deg() == 0 {
+        return vec![];
+    }
+
+    let x = Polynomial::x();
+    let g = gcd(&(x.pow_mod(P, f) - x), f);
+    debug!("gcd(x^p - x, f) = {g:?}");
+    if g.is_one() {
+        return vec![];
+    }
+
+    let mut roots = equal_degree_factorization(&g, 1, rng)
+        .into_iter()
+        .map(|h| -h.coef_at(0))
+        .collect_vec();
+    roots.sort();
+    roots
+}
+
+impl<const P: Natural> Polynomial<Finite<P>> {
+    /// The distinct roots of `self` in `F_p`, see [`roots_over_finite_field`].
+    pub fn roots(&self, rng: &mut impl Rng) -> Vec<Finite<P>> {
+        roots_over_finite_field(self, rng)
+    }
+}
+
+/// Rabin's irreducibility test (Section 14.9).
+///
+/// A polynomial `f ∈ F_p[x]` of degree `n > 0` is irreducible if and only if
+/// `x^(pⁿ) ≡ x mod f` and `gcd(x^(p^(n/t)) - x, f) = 1` for all prime
+/// divisors `t` of `n`.
+pub fn is_irreducible<const P: Natural>(f: &Polynomial<Finite<P>>) -> bool {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(14.9) Irreducibility test",
+        f = format!("{f:?}"),
+        p = P.to_string(),
+    );
+    let _enter = scope.enter();
+
+    let n = f.deg();
+    if n == 0 {
+        return false;
+    }
+    let f = f.scale(&f.lc().multiplicative_inverse().unwrap());
+    let divisors = prime_divisors(n);
+
+    let x = Polynomial::x();
+    let mut h = x.clone();
+    for i in 1..=n {
+        // h = x^(pⁱ) rem f
+        h = h.pow_mod(P, &f);
+        if divisors.iter().any(|t| i == n / t) {
+            let g = gcd(&(h.clone() - x.clone()), &f);
+            debug!("gcd(x^(p^{i}) - x, f) = {g:?}");
+            if !g.is_one() {
+                return false;
+            }
+        }
+    }
+    debug!("x^(p^{n}) rem f = {h:?}");
+
+    h == x % f
+}
+
+/// All monic irreducible polynomials of degree `n > 0` in `F_p[x]`, in
+/// lexicographic order of their coefficients.
+pub fn monic_irreducibles<const P: Natural>(n: Natural) -> Vec<Polynomial<Finite<P>>> {
+    let count = P.pow(n as u32);
+    (0..count)
+        .map(|mut k| {
+            let mut coefficients = (0..n)
+                .map(|_| {
+                    let c = Finite::from(k % P);
+                    k /= P;
+                    c
+                })
+                .collect_vec();
+            coefficients.push(Finite::one());
+            Polynomial::new(coefficients)
+        })
+        .filter(is_irreducible)
+        .collect()
+}
+
+/// A random monic irreducible polynomial of degree `n > 0` in `F_p[x]`.
+///
+/// About one in `n` monic polynomials of degree `n` is irreducible, so this
+/// takes `n` attempts of [`is_irreducible`] on average.
+pub fn random_irreducible<const P: Natural>(
+    n: Natural,
+    rng: &mut impl Rng,
+) -> Polynomial<Finite<P>> {
+    loop {
+        let f = random_polynomial(n, rng) + Polynomial::x().pow(n);
+        if is_irreducible(&f) {
+            return f;
+        }
+    }
+}
+
+/// Gauss's formula for the number `Iₙ = (1/n) Σ_{d | n} μ(d) p^(n/d)` of monic
+/// irreducible polynomials of degree `n > 0` in `F_p[x]`.
+pub fn number_of_monic_irreducibles(p: Natural, n: Natural) -> Natural {
+    let sum: Integer = (1..=n)
+        .filter(|d| n.is_multiple_of(*d))
+        .map(|d| mobius(d) * p.pow((n / d) as u32) as Integer)
+        .sum();
+    sum as Natural / n
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::Rational;
+
+    fn check<F: Field>(f: &Polynomial<F>, a: &[Polynomial<F>]) {
+        let product = a.iter().enumerate().fold(Polynomial::one(), |acc, (i, h)| {
+            acc * h.pow(i as Natural + 1)
+        });
+        assert_eq!(&product, f);
+
+        for (i, h) in a.iter().enumerate() {
+            assert!(h.is_monic());
+            assert!(
+                gcd(h, &h.diff()).is_one() || h.is_one(),
+                "{h:?} is not squarefree"
+            );
+            for k in a.iter().skip(i + 1) {
+                assert!(gcd(h, k).is_one());
+            }
+        }
+    }
+
+    #[test]
+    fn yun() {
+        let poly = |cs: &[i128]| Polynomial::new(cs.iter().map(|&c| Rational::from(c)).collect());
+
+        // (x + 1)(x - 2)²(x² + 1)³
+        let f = poly(&[1, 1]) * poly(&[-2, 1]).pow(2) * poly(&[1, 0, 1]).pow(3);
+        let a = yuns_squarefree_factorization(&f);
+
+        assert_eq!(a, [poly(&[1, 1]), poly(&[-2, 1]), poly(&[1, 0, 1])]);
+        check(&f, &a);
+    }
+
+    #[test]
+    #[should_panic(expected = "monic")]
+    fn yun_rejects_non_monic() {
+        // 2x + 2 would leave v = 2 ≠ 1 in step 4 forever
+        let f = Polynomial::new(vec![Rational::from(2), Rational::from(2)]);
+        yuns_squarefree_factorization(&f);
+    }
+
+    #[test]
+    fn finite_field() {
+        type R = Finite<3>;
+        let poly = |cs: &[i128]| Polynomial::new(cs.iter().map(|&c| R::from(c)).collect());
+
+        // x(x + 1)³(x + 2)⁴(x² + 1)⁶
+        let f =
+            poly(&[0, 1]) * poly(&[1, 1]).pow(3) * poly(&[2, 1]).pow(4) * poly(&[1, 0, 1]).pow(6);
+        let a = squarefree_factorization_over_finite_field(&f);
+
+        assert_eq!(a.len(), 6);
+        assert_eq!(a[0], poly(&[0, 1]));
+        assert_eq!(a[2], poly(&[1, 1]));
+        assert_eq!(a[3], poly(&[2, 1]));
+        assert_eq!(a[5], poly(&[1, 0, 1]));
+        check(&f, &a);
+    }
+
+    fn poly<const P: Natural>(cs: &[i128]) -> Polynomial<Finite<P>> {
+        Polynomial::new(cs.iter().map(|&c| Finite::from(c)).collect())
+    }
+
+    #[test]
+    fn distinct_degree() {
+        // x(x + 1)(x² + 1)(x³ - x + 1) over F_3
+        let f = poly::<3>(&[0, 1]) * poly(&[1, 1]) * poly(&[1, 0, 1]) * poly(&[1, -1, 0, 1]);
+
+        assert_eq!(
+            distinct_degree_factorization(&f),
+            [poly(&[0, 1, 1]), poly(&[1, 0, 1]), poly(&[1, -1, 0, 1])]
+        );
+    }
+
+    #[test]
+    fn factor_over_f2() {
+        let mut rng = StdRng::seed_from_u64(14);
+        // x¹⁵ - 1 = (x + 1)(x² + x + 1)(x⁴ + x + 1)(x⁴ + x³ + 1)(x⁴ + x³ + x² + x + 1)
+        let f = poly::<2>(&[1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+        for algorithm in [
+            SplittingAlgorithm::CantorZassenhaus,
+            SplittingAlgorithm::Berlekamp,
+        ] {
+            let factorization = factorization_over_finite_field(&f, algorithm, &mut rng);
+
+            assert_eq!(factorization.expand(), f);
+            assert_eq!(
+                factorization.factors,
+                [
+                    (poly(&[1, 1]), 1),
+                    (poly(&[1, 1, 1]), 1),
+                    (poly(&[1, 0, 0, 1, 1]), 1),
+                    (poly(&[1, 1, 0, 0, 1]), 1),
+                    (poly(&[1, 1, 1, 1, 1]), 1),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn factor_with_multiplicities() {
+        let mut rng = StdRng::seed_from_u64(5);
+        // 3(x⁸ - 1)(x + 1)² over F_5, where x⁴ + 1 = (x² + 2)(x² + 3)
+        let f = poly::<5>(&[-3, 0, 0, 0, 0, 0, 0, 0, 3]) * poly(&[1, 1]).pow(2);
+
+        for algorithm in [
+            SplittingAlgorithm::CantorZassenhaus,
+            SplittingAlgorithm::Berlekamp,
+        ] {
+            let factorization = factorization_over_finite_field(&f, algorithm, &mut rng);
+
+            assert_eq!(factorization.unit, Finite::from(3i128));
+            assert_eq!(factorization.expand(), f);
+            assert_eq!(
+                factorization.factors,
+                [
+                    (poly(&[1, 1]), 3),
+                    (poly(&[2, 1]), 1),
+                    (poly(&[3, 1]), 1),
+                    (poly(&[4, 1]), 1),
+                    (poly(&[2, 0, 1]), 1),
+                    (poly(&[3, 0, 1]), 1),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn irreducibility() {
+        assert!(is_irreducible(&poly::<2>(&[1, 1, 0, 0, 1])));
+        assert!(!is_irreducible(&poly::<2>(&[1, 0, 0, 0, 1])));
+        // x⁴ + x² + 1 = (x² + x + 1)² has no roots but is reducible
+        assert!(!is_irreducible(&poly::<2>(&[1, 0, 1, 0, 1])));
+        assert!(is_irreducible(&poly::<3>(&[2, 2, 0, 0, 0, 0])));
+        assert!(is_irreducible(&poly::<3>(&[1, -1, 0, 1])));
+        assert!(!is_irreducible(&poly::<5>(&[1, 0, 0, 0, 1])));
+        assert!(!is_irreducible(&poly::<5>(&[3])));
+    }
+
+    #[test]
+    fn enumeration() {
+        assert_eq!(
+            monic_irreducibles::<2>(3),
+            [poly(&[1, 1, 0, 1]), poly(&[1, 0, 1, 1])]
+        );
+
+        for n in 1..=6 {
+            assert_eq!(
+                monic_irreducibles::<2>(n).len() as Natural,
+                number_of_monic_irreducibles(2, n)
+            );
+        }
+        for n in 1..=4 {
+            assert_eq!(
+                monic_irreducibles::<3>(n).len() as Natural,
+                number_of_monic_irreducibles(3, n)
+            );
+        }
+        assert_eq!(number_of_monic_irreducibles(2, 12), 335);
+    }
+
+    #[test]
+    fn random_irreducibles() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let f = random_irreducible::<7>(5, &mut rng);
+
+        assert_eq!(f.deg(), 5);
+        assert!(f.is_monic());
+        let factorization =
+            factorization_over_finite_field(&f, SplittingAlgorithm::Berlekamp, &mut rng);
+        assert_eq!(factorization.factors, [(f, 1)]);
+    }
+
+    #[test]
+    fn roots() {
+        let mut rng = StdRng::seed_from_u64(5);
+        // (x - 1)(x - 3)²(x² + 1) over F_7
+        let f = poly::<7>(&[-1, 1]) * poly(&[-3, 1]).pow(2) * poly(&[1, 0, 1]);
+
+        assert_eq!(
+            f.roots(&mut rng),
+            [Finite::from(1i128), Finite::from(3i128)]
+        );
+        assert_eq!(poly::<7>(&[1, 0, 1]).roots(&mut rng), []);
+
+        let g = poly::<65537>(&[-4, 0, 1]) * poly(&[1, 0, 1]);
+        // 2¹⁶ ≡ -1, so ±2⁸ are the square roots of -1
+        assert_eq!(
+            g.roots(&mut rng),
+            [2, 256, 65537 - 256, 65537 - 2].map(|c: Natural| Finite::from(c))
+        );
+    }
+}
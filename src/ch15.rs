@@ -0,0 +1,577 @@
+//! # Hensel lifting and factoring polynomials
+
+use itertools::Itertools;
+use rand::Rng;
+use tracing::debug;
+
+use crate::{
+    ch03::{extended_euclidean_algorithm, gcd},
+    ch06::{coefficient_bound, integer_gcd},
+    ch14::{factorization_over_finite_field, Factorization, SplittingAlgorithm},
+    finite::{with_prime, PRIMES},
+    Finite, Group, Integer, Natural, Polynomial, Rational, Ring,
+};
+
+/// `f mod m` with symmetric representatives in `(-m/2, m/2]`.
+pub(crate) fn symmetric_mod(f: &Polynomial<Integer>, m: Integer) -> Polynomial<Integer> {
+    Polynomial::new(
+        f.iter()
+            .map(|(c, _)| {
+                let c = c.rem_euclid(m);
+                if c > m / 2 {
+                    c - m
+                } else {
+                    c
+                }
+            })
+            .collect(),
+    )
+}
+
+/// Division with remainder modulo `m` by a monic `b`, reducing after every
+/// step so that the coefficients stay below `m²`.
+fn div_rem_monic(
+    a: &Polynomial<Integer>,
+    b: &Polynomial<Integer>,
+    m: Integer,
+) -> (Polynomial<Integer>, Polynomial<Integer>) {
+    let mut r = symmetric_mod(a, m);
+    if r.deg() < b.deg() || r.is_zero() {
+        return (Polynomial::zero(), r);
+    }
+
+    let b = symmetric_mod(b, m);
+    let mut q = vec![0; (r.deg() - b.deg() + 1) as usize];
+    for i in (0..q.len()).rev() {
+        q[i] = r.coef_at(b.deg() + i as Natural);
+        r = symmetric_mod(&(r - b.times_x(i as Natural).scale(&q[i])), m);
+    }
+    (Polynomial::new(q), r)
+}
+
+/// Whether the Hensel lifting modulo `pl` for `f` of degree `n` stays within
+/// the range of [`Integer`], where products of two polynomials with
+/// coefficients in `(-pl/2, pl/2]` have coefficients up to `(n + 1) pl²/4`.
+pub(crate) fn lifting_fits(pl: Integer, n: Natural) -> bool {
+    pl.checked_mul(pl)
+        .and_then(|s| s.checked_mul(n as Integer + 1))
+        .is_some()
+}
+
+/// `u · f₁ ··· fᵣ mod m`, reducing after every multiplication.
+pub(crate) fn product_mod<'a>(
+    u: Integer,
+    factors: impl IntoIterator<Item = &'a Polynomial<Integer>>,
+    m: Integer,
+) -> Polynomial<Integer> {
+    factors
+        .into_iter()
+        .fold(symmetric_mod(&Polynomial::new(vec![u]), m), |acc, g| {
+            symmetric_mod(&(acc * g.clone()), m)
+        })
+}
+
+/// The inverse of `a` modulo `m`, for `a` coprime to `m`.
+fn inverse_mod(a: Integer, m: Integer) -> Integer {
+    let eea = extended_euclidean_algorithm(&a.rem_euclid(m), &m);
+    let l = eea.r.len() - 2;
+    assert_eq!(eea.r[l].abs(), 1, "{a} is not invertible modulo {m}");
+
+    (eea.s[l] * eea.r[l]).rem_euclid(m)
+}
+
+/// Mignotte's bound `B = ⌈(n + 1)^(1/2) 2ⁿ ‖f‖∞ |lc(f)|⌉` from Algorithm
+/// 15.19, or `None` if it exceeds the range of [`Natural`].
+///
+/// Any factorization `lc(f) f = g h` into `g, h ∈ Z[x]` with
+/// `lc(g) = lc(h) = lc(f)` has `‖g‖₁ ‖h‖₁ ≤ B`, so lifting the modular
+/// factorization of `f` to a modulus above `2B` recovers all true factors.
+pub fn mignotte_bound(f: &Polynomial<Integer>) -> Option<Natural> {
+    coefficient_bound(f.deg(), f.max_norm(), f.lc().unsigned_abs())
+}
+
+/// The Hensel step modulo any divisor `modulus` of `m²` that is a multiple of
+/// `m`, which allows the last step of a lifting to stop at the target modulus.
+fn hensel_step_modulo(
+    modulus: Integer,
+    f: &Polynomial<Integer>,
+    [g, h, s, t]: [&Polynomial<Integer>; 4],
+) -> [Polynomial<Integer>; 4] {
+    let m = |f: Polynomial<Integer>| symmetric_mod(&f, modulus);
+
+    // 1.
+    let e = m(f.clone() - g * h);
+    let (q, r) = div_rem_monic(&m(s * &e), h, modulus);
+    let g_star = m(g.clone() + m(t * &e) + m(&q * g));
+    let h_star = m(h.clone() + r);
+
+    // 2.
+    let b = m(m(s * &g_star) + m(t * &h_star) - Integer::one());
+    let (c, d) = div_rem_monic(&m(s * &b), &h_star, modulus);
+    let s_star = m(s.clone() - d);
+    let t_star = m(t.clone() - m(t * &b) - m(c * &g_star));
+
+    [g_star, h_star, s_star, t_star]
+}
+
+/// Algorithm 15.10 Hensel step.
+///
+/// - Input: `m ∈ Z` and `f, g, h, s, t ∈ Z[x]` with `f ≡ gh mod m` and
+///   `sg + th ≡ 1 mod m`, where `h` is monic, `deg f = deg g + deg h`,
+///   `deg s < deg h` and `deg t < deg g`.
+/// - Output: `[g*, h*, s*, t*]` with `g* ≡ g mod m`, `h* ≡ h mod m`,
+///   `s* ≡ s mod m`, `t* ≡ t mod m` and the same properties modulo `m²`.
+pub fn hensel_step(
+    m: Integer,
+    f: &Polynomial<Integer>,
+    g: &Polynomial<Integer>,
+    h: &Polynomial<Integer>,
+    s: &Polynomial<Integer>,
+    t: &Polynomial<Integer>,
+) -> [Polynomial<Integer>; 4] {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(15.10) Hensel step",
+        m = m.to_string(),
+        f = format!("{f:?}"),
+        g = format!("{g:?}"),
+        h = format!("{h:?}"),
+    );
+    let _enter = scope.enter();
+
+    let res = hensel_step_modulo(m * m, f, [g, h, s, t]);
+    debug!("g* = {:?}, h* = {:?}", res[0], res[1]);
+    res
+}
+
+/// `s, t` with `sg + th ≡ 1 mod P`, `deg s < deg h` and `deg t < deg g`.
+fn bezout_coefficients<const P: Natural>(
+    g: &Polynomial<Integer>,
+    h: &Polynomial<Integer>,
+) -> [Polynomial<Integer>; 2] {
+    let eea = extended_euclidean_algorithm(
+        &Polynomial::<Finite<P>>::from(g.clone()),
+        &Polynomial::<Finite<P>>::from(h.clone()),
+    );
+    let l = eea.r.len() - 2;
+    assert_eq!(eea.r[l].deg(), 0, "the factors are not coprime modulo {P}");
+    let u = eea.r[l].lc().multiplicative_inverse().unwrap();
+
+    [eea.s[l].scale(&u).into(), eea.t[l].scale(&u).into()]
+}
+
+/// Algorithm 15.17 Multifactor Hensel lifting.
+///
+/// - Input: a prime `p`, `l ≥ 1`, `f ∈ Z[x]` with `lc(f)` a unit modulo
+///   `p`, and monic `f₁, ..., fᵣ ∈ Z[x]` pairwise coprime modulo `p` with
+///   `f ≡ lc(f) f₁ ··· fᵣ mod p`.
+/// - Output: monic `f₁*, ..., fᵣ*` with `fᵢ* ≡ fᵢ mod p` and
+///   `f ≡ lc(f) f₁* ··· fᵣ* mod pˡ`.
+///
+/// The factors are split into two halves which are lifted with
+/// [`hensel_step`]s and then recursively lifted further.
+pub fn multifactor_hensel_lifting(
+    p: Natural,
+    l: Natural,
+    f: &Polynomial<Integer>,
+    factors: &[Polynomial<Integer>],
+) -> Vec<Polynomial<Integer>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(15.17) Multifactor Hensel lifting",
+        p = p.to_string(),
+        l = l.to_string(),
+        f = format!("{f:?}"),
+        factors = format!("{factors:?}"),
+    );
+    let _enter = scope.enter();
+
+    let pl = (p as Integer).pow(l as u32);
+
+    // 1.
+    if factors.len() == 1 {
+        let u = inverse_mod(f.lc(), pl);
+        return vec![symmetric_mod(&f.scale(&u), pl)];
+    }
+
+    // 2.
+    let k = factors.len() / 2;
+    let p = p as Integer;
+    let mut g = product_mod(f.lc(), &factors[..k], p);
+    let mut h = product_mod(1, &factors[k..], p);
+
+    // 3.
+    let [mut s, mut t] = with_prime!(p as Natural, P => bezout_coefficients::<P>(&g, &h));
+
+    // 4.
+    let mut m = p;
+    while m < pl {
+        m = m.checked_mul(m).map_or(pl, |m| m.min(pl));
+        [g, h, s, t] = hensel_step_modulo(m, f, [&g, &h, &s, &t]);
+        debug!("m = {m}: g = {g:?}, h = {h:?}");
+    }
+
+    // 5.
+    let mut lifted = multifactor_hensel_lifting(p as Natural, l, &g, &factors[..k]);
+    lifted.extend(multifactor_hensel_lifting(
+        p as Natural,
+        l,
+        &h,
+        &factors[k..],
+    ));
+    lifted
+}
+
+/// The monic irreducible factors of `f mod P`, or `None` if `f mod P` is not
+/// squarefree.
+pub(crate) fn modular_factors<const P: Natural>(
+    f: &Polynomial<Integer>,
+    rng: &mut impl Rng,
+) -> Option<Vec<Polynomial<Integer>>> {
+    let f = Polynomial::<Finite<P>>::from(f.clone());
+    if !gcd(&f, &f.diff()).is_one() {
+        return None;
+    }
+
+    let factorization =
+        factorization_over_finite_field(&f, SplittingAlgorithm::CantorZassenhaus, rng);
+    Some(
+        factorization
+            .factors
+            .iter()
+            .map(|(g, _)| g.clone().into())
+            .collect(),
+    )
+}
+
+/// Algorithm 15.19 Factorization in `Z[x]` (Zassenhaus's algorithm).
+///
+/// - Input: squarefree primitive `f ∈ Z[x]` of degree `n ≥ 1` with
+///   `lc(f) > 0`.
+/// - Output: the irreducible factors of `f`, primitive with positive leading
+///   coefficients, or `None` if the lifting modulo `pˡ` would exceed the
+///   range of [`Integer`].
+///
+/// The prime `p` is the first of [`PRIMES`] not dividing `lc(f)` for which
+/// `f mod p` is squarefree. The modular factors are lifted to `pˡ > 2B`
+/// with `B` the [`mignotte_bound`], and the true factors are then found by
+/// searching through the subsets of lifted factors in order of size.
+pub fn zassenhaus_factorization(
+    f: &Polynomial<Integer>,
+    rng: &mut impl Rng,
+) -> Option<Vec<Polynomial<Integer>>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(15.19) Factorization in Z[x]",
+        f = format!("{f:?}"),
+    );
+    let _enter = scope.enter();
+
+    // 1.
+    let n = f.deg();
+    if n == 1 {
+        return Some(vec![f.clone()]);
+    }
+    let mut b = f.lc();
+    let bound = mignotte_bound(f)?;
+
+    // 2.
+    let (p, modular) = PRIMES
+        .into_iter()
+        .filter(|p| b % *p as Integer != 0)
+        .find_map(|p| with_prime!(p, P => modular_factors::<P>(f, rng)).map(|h| (p, h)))
+        .expect("f is squarefree modulo some prime");
+    let (mut l, mut pl) = (1, p as Integer);
+    while pl.unsigned_abs() / 2 <= bound {
+        pl = pl.checked_mul(p as Integer)?;
+        l += 1;
+    }
+    debug!("B = {bound}, p = {p}, l = {l}");
+    if !lifting_fits(pl, n) {
+        debug!("the lifting modulo {pl} exceeds the range of Integer");
+        return None;
+    }
+
+    // 3.
+    debug!("f ≡ {b} · {modular:?} mod {p}");
+
+    // 4.
+    let mut lifted = multifactor_hensel_lifting(p, l, f, &modular);
+    debug!("f ≡ {b} · {lifted:?} mod {pl}");
+
+    // 5.
+    let mut factors = vec![];
+    let mut f_star = f.clone();
+    let mut s = 1;
+    while 2 * s <= lifted.len() {
+        let found = (0..lifted.len()).combinations(s).find_map(|subset| {
+            let g_star = product_mod(b, subset.iter().map(|&i| &lifted[i]), pl);
+            let h_star = product_mod(
+                b,
+                (0..lifted.len())
+                    .filter(|i| !subset.contains(i))
+                    .map(|i| &lifted[i]),
+                pl,
+            );
+
+            (g_star.one_norm().saturating_mul(h_star.one_norm()) <= bound)
+                .then_some((subset, g_star, h_star))
+        });
+
+        match found {
+            Some((subset, g_star, h_star)) => {
+                debug!("true factor {g_star:?} from {subset:?}");
+                lifted = lifted
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(i, _)| !subset.contains(i))
+                    .map(|(_, g)| g)
+                    .collect();
+                factors.push(g_star.primitive_part());
+                f_star = h_star.primitive_part();
+                b = f_star.lc();
+            }
+            None => s += 1,
+        }
+    }
+
+    // 6.
+    factors.push(f_star);
+    Some(factors)
+}
+
+/// The primitive part of `f ∈ Q[x]` after clearing denominators.
+pub(crate) fn integer_primitive_part(f: &Polynomial<Rational>) -> Polynomial<Integer> {
+    let l = f.iter().fold(1 as Integer, |l, (c, _)| {
+        let d = c.denom as Integer;
+        l / gcd(&l, &d) * d
+    });
+
+    Polynomial::new(
+        f.iter()
+            .map(|(c, _)| c.num * (l / c.denom as Integer))
+            .collect(),
+    )
+    .primitive_part()
+}
+
+/// The quotient `a / b` in `Z[x]`, for `b` dividing `a`.
+fn exact_quotient(a: &Polynomial<Integer>, b: &Polynomial<Integer>) -> Polynomial<Integer> {
+    if a.is_zero() {
+        return Polynomial::zero();
+    }
+
+    let m = b.deg();
+    let mut r = a.clone();
+    let mut q = vec![0; (a.deg() - m + 1) as usize];
+    for i in (0..q.len()).rev() {
+        q[i] = r.coef_at(m + i as Natural) / b.lc();
+        r = r - b.times_x(i as Natural).scale(&q[i]);
+    }
+    debug_assert!(r.is_zero(), "{b:?} does not divide {a:?}");
+
+    Polynomial::new(q)
+}
+
+/// Yun's squarefree factorization (Algorithm 14.21) in `Z[x]`.
+///
+/// - Input: primitive `f ∈ Z[x]` of degree `n > 0` with `lc(f) > 0`.
+/// - Output: `(a₁, ..., aₘ)` with `f = a₁ a₂² ··· aₘᵐ`, where the `aᵢ` are
+///   squarefree, pairwise coprime and primitive with positive leading
+///   coefficients.
+///
+/// By Gauss's lemma all the divisions of Yun's algorithm by primitive gcds
+/// are exact in `Z[x]`, which avoids the coefficient growth of computing over
+/// `Q`.
+pub fn squarefree_factorization_over_integers(f: &Polynomial<Integer>) -> Vec<Polynomial<Integer>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "Squarefree factorization over Z",
+        f = format!("{f:?}"),
+    );
+    let _enter = scope.enter();
+
+    // 1.
+    let u = integer_gcd(f, &f.diff());
+    let mut v = exact_quotient(f, &u);
+    let mut w = exact_quotient(&f.diff(), &u);
+
+    // 2.
+    let mut a = vec![];
+    loop {
+        // 3.
+        let y = w - v.diff();
+        let h = integer_gcd(&v, &y);
+        v = exact_quotient(&v, &h);
+        w = exact_quotient(&y, &h);
+        debug!("a{} = {h:?}", a.len() + 1);
+        a.push(h);
+
+        // 4.
+        if v.deg() == 0 {
+            break;
+        }
+    }
+
+    a
+}
+
+/// Factorization in `Z[x]`.
+///
+/// - Input: nonzero `f ∈ Z[x]`.
+/// - Output: the factorization of `f` into its content, with the sign of
+///   `lc(f)`, and primitive irreducible factors with positive leading
+///   coefficients and their multiplicities, ordered by degree, or `None` if
+///   the coefficients are too large for [`zassenhaus_factorization`].
+///
+/// Each part of the [`squarefree_factorization_over_integers`] of the
+/// primitive part is factored with [`zassenhaus_factorization`].
+pub fn factorization_over_integers(
+    f: &Polynomial<Integer>,
+    rng: &mut impl Rng,
+) -> Option<Factorization<Integer>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "Factorization over Z",
+        f = format!("{f:?}"),
+    );
+    let _enter = scope.enter();
+
+    assert!(!f.is_zero(), "cannot factor zero");
+
+    let unit = f.content();
+    let mut factors = vec![];
+    if f.deg() > 0 {
+        for (i, a) in squarefree_factorization_over_integers(&f.primitive_part())
+            .iter()
+            .enumerate()
+        {
+            if a.deg() == 0 {
+                continue;
+            }
+
+            let irreducibles = zassenhaus_factorization(a, rng)?;
+            debug!("a{} = {irreducibles:?}", i + 1);
+            factors.extend(irreducibles.into_iter().map(|g| (g, i as Natural + 1)));
+        }
+    }
+    factors.sort_by_key(|(g, _)| (g.deg(), g.iter().map(|(c, _)| *c).collect_vec()));
+
+    Some(Factorization { unit, factors })
+}
+
+/// Factorization in `Q[x]`.
+///
+/// - Input: nonzero `f ∈ Q[x]`.
+/// - Output: the factorization of `f` into its leading coefficient and monic
+///   irreducible factors with multiplicities, ordered by degree, or `None`
+///   as for [`factorization_over_integers`].
+///
+/// The denominators are cleared and the result is factored with
+/// [`factorization_over_integers`].
+pub fn factorization_over_rationals(
+    f: &Polynomial<Rational>,
+    rng: &mut impl Rng,
+) -> Option<Factorization<Rational>> {
+    let integer = factorization_over_integers(&integer_primitive_part(f), rng)?;
+
+    Some(Factorization {
+        unit: f.lc(),
+        factors: integer
+            .factors
+            .into_iter()
+            .map(|(g, e)| {
+                let lc = Rational::from(g.lc());
+                (g.map_coefficients(|c| Rational::from(*c) / lc), e)
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn poly(cs: &[Integer]) -> Polynomial<Integer> {
+        Polynomial::new(cs.to_vec())
+    }
+
+    #[test]
+    fn lifting() {
+        // x⁴ - 1 ≡ (x - 1)(x - 2)(x + 2)(x + 1) mod 5
+        let f = poly(&[-1, 0, 0, 0, 1]);
+        let factors = [poly(&[-1, 1]), poly(&[-2, 1]), poly(&[2, 1]), poly(&[1, 1])];
+        let lifted = multifactor_hensel_lifting(5, 4, &f, &factors);
+
+        assert_eq!(product_mod(1, &lifted, 625), f);
+        for (g, h) in lifted.iter().zip(&factors) {
+            assert!(g.is_monic());
+            assert_eq!(symmetric_mod(g, 5), symmetric_mod(h, 5));
+        }
+        // the square roots of -1 modulo 625
+        assert_eq!(lifted[1], poly(&[-182, 1]));
+        assert_eq!(lifted[2], poly(&[182, 1]));
+    }
+
+    #[test]
+    fn integers() {
+        let mut rng = StdRng::seed_from_u64(15);
+        // -2(x - 2)²(3x + 1)(x² + 1)(x⁴ + 1), where x⁴ + 1 is reducible modulo
+        // every prime
+        let factors = [
+            poly(&[-2, 1]),
+            poly(&[1, 3]),
+            poly(&[1, 0, 1]),
+            poly(&[1, 0, 0, 0, 1]),
+        ];
+        let f = poly(&[-2])
+            * factors[0].pow(2)
+            * factors[1].clone()
+            * factors[2].clone()
+            * factors[3].clone();
+        let factorization = factorization_over_integers(&f, &mut rng).unwrap();
+
+        assert_eq!(factorization.unit, -2);
+        assert_eq!(factorization.expand(), f);
+        assert_eq!(
+            factorization.factors,
+            [
+                (factors[0].clone(), 2),
+                (factors[1].clone(), 1),
+                (factors[2].clone(), 1),
+                (factors[3].clone(), 1),
+            ]
+        );
+
+        // Swinnerton-Dyer
+        let g = poly(&[1, 0, -10, 0, 1]);
+        assert_eq!(zassenhaus_factorization(&g, &mut rng), Some(vec![g]));
+
+        // Mignotte's bound for x⁶⁴ + x + 1 is far beyond the Hensel lifting
+        let mut cs = vec![0; 65];
+        (cs[0], cs[1], cs[64]) = (1, 1, 1);
+        assert!(factorization_over_integers(&poly(&cs), &mut rng).is_none());
+    }
+
+    #[test]
+    fn rationals() {
+        let mut rng = StdRng::seed_from_u64(15);
+        let r = |num, denom| Rational { num, denom };
+        // 1/2 (x² - 1/4)(x + 2/3)
+        let f = Polynomial::new(vec![r(-1, 12), r(-1, 8), r(1, 3), r(1, 2)]);
+        let factorization = factorization_over_rationals(&f, &mut rng).unwrap();
+
+        assert_eq!(factorization.unit, r(1, 2));
+        assert_eq!(
+            factorization.factors,
+            [
+                (Polynomial::new(vec![r(-1, 2), r(1, 1)]), 1),
+                (Polynomial::new(vec![r(1, 2), r(1, 1)]), 1),
+                (Polynomial::new(vec![r(2, 3), r(1, 1)]), 1),
+            ]
+        );
+        assert_eq!(factorization.expand(), f);
+    }
+}
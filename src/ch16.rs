@@ -0,0 +1,523 @@
+//! # Short vectors in lattices
+
+use rand::Rng;
+use tracing::debug;
+
+use crate::{
+    ch15::{
+        lifting_fits, mignotte_bound, modular_factors, multifactor_hensel_lifting, product_mod,
+        symmetric_mod, zassenhaus_factorization,
+    },
+    finite::{with_prime, PRIMES},
+    integers::mul_mod,
+    matrix::Matrix,
+    Group, Integer, Polynomial, Rational, Ring,
+};
+
+/// The reduction parameter `δ = 3/4` of Lenstra, Lenstra and Lovász.
+pub const DEFAULT_DELTA: Rational = Rational { num: 3, denom: 4 };
+
+fn dot(u: &[Integer], v: &[Integer]) -> Integer {
+    u.iter().zip(v).map(|(a, b)| a * b).sum()
+}
+
+/// Algorithm 16.10 Basis reduction (the LLL algorithm).
+///
+/// The basis is kept size reduced, `|μᵢⱼ| ≤ 1/2` for `j < i`, and two
+/// neighbours `gₖ₋₁, gₖ` are swapped whenever the Lovász condition
+/// `‖gₖ*‖² ≥ (δ - μₖ,ₖ₋₁²) ‖gₖ₋₁*‖²` fails. The Gram–Schmidt coefficients
+/// `μᵢⱼ = ⟨gᵢ, gⱼ*⟩ / ‖gⱼ*‖²` and the squared norms `‖gᵢ*‖²` are exact
+/// rationals, which are updated after each step instead of being
+/// recomputed.
+#[derive(Debug, Clone)]
+pub struct BasisReduction {
+    /// The reduced basis `g₁, ..., gₙ`.
+    pub basis: Vec<Vec<Integer>>,
+    /// The Gram–Schmidt coefficients `μᵢⱼ` for `j < i`.
+    pub mu: Vec<Vec<Rational>>,
+    /// The squared norms `‖gᵢ*‖²` of the Gram–Schmidt orthogonalization.
+    pub norms: Vec<Rational>,
+    pub swaps: usize,
+    pub size_reductions: usize,
+}
+
+impl BasisReduction {
+    /// - Input: linearly independent `f₁, ..., fₙ ∈ Zᵐ` and `1/4 < δ < 1`.
+    /// - Output: a reduced basis `g₁, ..., gₙ` of the same lattice, where
+    ///   `‖g₁‖² ≤ (1/(δ - 1/4))ⁿ⁻¹ ‖x‖²` for every nonzero `x` in the
+    ///   lattice.
+    pub fn run(f: &[Vec<Integer>], delta: Rational) -> Self {
+        let scope = tracing::span!(
+            tracing::Level::DEBUG,
+            "(16.10) Basis reduction",
+            f = format!("{f:?}"),
+            delta = format!("{delta:?}"),
+        );
+        let _enter = scope.enter();
+
+        assert!(
+            Rational { num: 1, denom: 4 } < delta && delta < Rational::one(),
+            "δ must lie in (1/4, 1)"
+        );
+
+        // 1.
+        let n = f.len();
+        let mut g = f.to_vec();
+        let mut mu = vec![vec![Rational::zero(); n]; n];
+        let mut b = vec![Rational::zero(); n];
+        for i in 0..n {
+            for j in 0..i {
+                let s = (0..j).fold(Rational::from(dot(&g[i], &g[j])), |s, l| {
+                    s - mu[j][l] * (mu[i][l] * b[l])
+                });
+                mu[i][j] = s / b[j];
+            }
+            b[i] = (0..i).fold(Rational::from(dot(&g[i], &g[i])), |s, l| {
+                s - mu[i][l] * (mu[i][l] * b[l])
+            });
+            assert!(!b[i].is_zero(), "the vectors are linearly dependent");
+        }
+        debug!("‖g*‖² = {b:?}");
+
+        // 2.
+        let (mut swaps, mut size_reductions) = (0, 0);
+        let mut k = 1;
+        while k < n {
+            // 3.
+            for j in (0..k).rev() {
+                let r = mu[k][j].round();
+                if r == 0 {
+                    continue;
+                }
+                for l in 0..g[k].len() {
+                    g[k][l] -= r * g[j][l];
+                }
+                let r = Rational::from(r);
+                let (lower, upper) = mu.split_at_mut(k);
+                for (a, c) in upper[0][..j].iter_mut().zip(&lower[j][..j]) {
+                    *a = *a - r * *c;
+                }
+                mu[k][j] = mu[k][j] - r;
+                size_reductions += 1;
+                debug!("g{k} ← g{k} - {r:?} g{j} = {:?}", g[k]);
+            }
+
+            // 4.
+            let m = mu[k][k - 1];
+            if b[k] >= (delta - m * m) * b[k - 1] {
+                k += 1;
+                continue;
+            }
+
+            // 5.
+            g.swap(k - 1, k);
+            let c = b[k] + m * (m * b[k - 1]);
+            let new_m = m * (b[k - 1] / c);
+            b[k] = (b[k - 1] / c) * b[k];
+            b[k - 1] = c;
+            mu[k][k - 1] = new_m;
+            let (lower, upper) = mu.split_at_mut(k);
+            lower[k - 1][..k - 1].swap_with_slice(&mut upper[0][..k - 1]);
+            for row in &mut mu[k + 1..] {
+                let t = row[k];
+                row[k] = row[k - 1] - m * t;
+                row[k - 1] = t + new_m * row[k];
+            }
+            swaps += 1;
+            debug!("swap g{} and g{k}: ‖g*‖² = {b:?}", k - 1);
+            k = (k - 1).max(1);
+        }
+
+        BasisReduction {
+            basis: g,
+            mu: (0..n).map(|i| mu[i][..i].to_vec()).collect(),
+            norms: b,
+            swaps,
+            size_reductions,
+        }
+    }
+}
+
+/// A short nonzero vector of the lattice spanned by `f`, namely the first
+/// vector of its [`BasisReduction`], which is at most `2⁽ⁿ⁻¹⁾ᐟ²` times as long
+/// as a shortest one for `δ = 3/4`.
+pub fn short_vector(f: &[Vec<Integer>], delta: Rational) -> Vec<Integer> {
+    BasisReduction::run(f, delta).basis.swap_remove(0)
+}
+
+/// A small integer relation `Σ cᵢ xᵢ ≈ 0` with `c ≠ 0`.
+///
+/// The lattice spanned by the rows `(eᵢ, round(scale · xᵢ))` contains
+/// `(c, round(scale · Σ cᵢ xᵢ))` up to rounding errors, which is short
+/// whenever `c` is small and `Σ cᵢ xᵢ` is close to zero. For the powers
+/// `1, α, ..., αⁿ` of an approximation of an algebraic number this finds its
+/// minimal polynomial, once `scale` is large enough.
+pub fn integer_relation(xs: &[Rational], scale: Integer, delta: Rational) -> Vec<Integer> {
+    let n = xs.len();
+    let f = xs
+        .iter()
+        .enumerate()
+        .map(|(i, x)| {
+            let mut row = vec![0; n + 1];
+            row[i] = 1;
+            row[n] = (Rational::from(scale) * *x).round();
+            row
+        })
+        .collect::<Vec<_>>();
+
+    let mut c = short_vector(&f, delta);
+    c.truncate(n);
+    debug!("relation {c:?}");
+    c
+}
+
+/// The number `K` of power sums of the roots used in [`factor_recombination`].
+const POWER_SUMS: usize = 2;
+
+/// The bounds `n (|b| + ‖f‖∞)ᵏ ≥ |bᵏ pₖ(g)|` for `k = 1, ..., K`, where `pₖ`
+/// is the `k`-th power sum of the roots of a factor `g` of `f` with
+/// `b = lc(f)`, from `|α| < 1 + ‖f‖∞ / |b|` for every root `α` of `f`, or
+/// `None` if they exceed the range of [`Integer`].
+fn power_sum_bounds(f: &Polynomial<Integer>) -> Option<[Integer; POWER_SUMS]> {
+    let root = f.lc().abs().checked_add(f.max_norm().try_into().ok()?)?;
+    let mut bounds = [0; POWER_SUMS];
+    for (k, bound) in bounds.iter_mut().enumerate() {
+        *bound = root
+            .checked_pow(k as u32 + 1)?
+            .checked_mul(f.deg() as Integer)?;
+    }
+    Some(bounds)
+}
+
+/// `bᵏ pₖ(u) mod m` for `k = 1, ..., K` and monic `u`, from Newton's
+/// identities `p₁ = -a₁` and `p₂ = a₁² - 2a₂` with `u = xᵈ + a₁xᵈ⁻¹ + a₂xᵈ⁻² + ···`.
+fn power_sums(u: &Polynomial<Integer>, b: Integer, m: Integer) -> [Integer; POWER_SUMS] {
+    let d = u.deg();
+    let a1 = u.coef_at(d - 1);
+    let a2 = if d >= 2 { u.coef_at(d - 2) } else { 0 };
+
+    let p2 = (mul_mod(a1, a1, m) - mul_mod(2, a2, m)).rem_euclid(m);
+    [mul_mod(-a1, b, m), mul_mod(p2, mul_mod(b, b, m), m)].map(
+        |p| {
+            if p > m / 2 {
+                p - m
+            } else {
+                p
+            }
+        },
+    )
+}
+
+/// The squared norm bound `r + K (r + 1)²` on the vectors of the
+/// recombination lattice coming from true factors.
+fn recombination_bound(r: usize) -> Integer {
+    let r = r as Integer;
+    r + POWER_SUMS as Integer * (r + 1) * (r + 1)
+}
+
+/// Factor recombination with short vectors (van Hoeij's algorithm).
+///
+/// - Input: squarefree primitive `f ∈ Z[x]` with `b = lc(f) > 0` and the
+///   monic `u₁, ..., uᵣ` with `f ≡ b u₁ ··· uᵣ mod pˡ` from
+///   [`multifactor_hensel_lifting`].
+/// - Output: the irreducible factors of `f`, or `None` if `pˡ` is too small
+///   to separate them or the power sums are too large.
+///
+/// A true factor `g ≡ lc(g) ∏_{i∈S} uᵢ` is described by its `0/1` vector
+/// `(cᵢ)` with `cᵢ = 1` for `i ∈ S`, and since the power sums of the roots are
+/// additive, `bᵏ pₖ(g) ≡ Σ cᵢ bᵏ pₖ(uᵢ) mod pˡ` is small. So the `0/1`
+/// vectors lie in the lattice spanned by the rows
+/// `(eᵢ, bᵏ pₖ(uᵢ) / Dₖ)` and `(0, pˡ / Dₖ eₖ)` and are short for `Dₖ` the
+/// bounds on `bᵏ pₖ(g)`. After [`BasisReduction`], the vectors with small
+/// Gram–Schmidt norms span the `0/1` vectors, which are read off from the
+/// reduced row echelon form of their first `r` coordinates, instead of
+/// searching through the `2ʳ` subsets like [`zassenhaus_factorization`].
+pub fn factor_recombination(
+    f: &Polynomial<Integer>,
+    lifted: &[Polynomial<Integer>],
+    pl: Integer,
+    delta: Rational,
+) -> Option<Vec<Polynomial<Integer>>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "Factor recombination",
+        f = format!("{f:?}"),
+        lifted = format!("{lifted:?}"),
+        pl = pl.to_string(),
+    );
+    let _enter = scope.enter();
+
+    // 1.
+    let (b, r) = (f.lc(), lifted.len());
+    if r == 1 {
+        return Some(vec![f.clone()]);
+    }
+    let bounds = power_sum_bounds(f)?;
+    let scaled = bounds.map(|d| (Rational::from(pl) / Rational::from(d)).round());
+    if scaled.contains(&0) {
+        debug!("pˡ is too small for the power sum bounds {bounds:?}");
+        return None;
+    }
+
+    // 2.
+    let mut rows = vec![];
+    for (i, u) in lifted.iter().enumerate() {
+        let mut row = vec![0; r + POWER_SUMS];
+        row[i] = 1;
+        for (k, p) in power_sums(u, b, pl).into_iter().enumerate() {
+            row[r + k] = (Rational::from(p) / Rational::from(bounds[k])).round();
+        }
+        rows.push(row);
+    }
+    for (k, d) in scaled.into_iter().enumerate() {
+        let mut row = vec![0; r + POWER_SUMS];
+        row[r + k] = d;
+        rows.push(row);
+    }
+
+    // 3.
+    let reduction = BasisReduction::run(&rows, delta);
+    let bound = Rational::from(recombination_bound(r));
+    let s = reduction
+        .norms
+        .iter()
+        .rposition(|b| *b <= bound)
+        .map_or(0, |j| j + 1);
+    debug!("{s} of {} reduced vectors are short", rows.len());
+
+    // 4.
+    let w = Matrix::from_fn(s, r, |i, j| Rational::from(reduction.basis[i][j]));
+    let (w, _) = w.reduced_row_echelon_form();
+    let subsets = (0..s)
+        .map(|i| (0..r).filter(|&j| !w[(i, j)].is_zero()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let is_partition = (0..s).all(|i| (0..r).all(|j| w[(i, j)].is_zero() || w[(i, j)].is_one()))
+        && (0..r).all(|j| subsets.iter().filter(|s| s.contains(&j)).count() == 1);
+    if !is_partition {
+        debug!("the short vectors do not span 0/1 vectors");
+        return None;
+    }
+
+    // 5.
+    let factors = subsets
+        .iter()
+        .map(|subset| {
+            let g = product_mod(b, subset.iter().map(|&i| &lifted[i]), pl);
+            symmetric_mod(&g, pl).primitive_part()
+        })
+        .collect::<Vec<_>>();
+    debug!("factors {factors:?} from {subsets:?}");
+
+    let product = factors.iter().fold(Polynomial::one(), |p, g| p * g.clone());
+    (product == *f).then_some(factors)
+}
+
+/// Factorization in `Z[x]` with short vectors.
+///
+/// - Input: squarefree primitive `f ∈ Z[x]` of degree `n ≥ 1` with
+///   `lc(f) > 0`.
+/// - Output: the irreducible factors of `f`, primitive with positive leading
+///   coefficients, or `None` if the coefficients of `f` are too large, as in
+///   [`zassenhaus_factorization`].
+///
+/// As in [`zassenhaus_factorization`] the modular factors are lifted, but
+/// to a modulus `pˡ` that also leaves room for the [`factor_recombination`]
+/// lattice, which replaces the subset search. If that modulus is out of
+/// range or the lattice fails to separate the factors, this falls back to
+/// [`zassenhaus_factorization`].
+pub fn factorization_with_short_vectors(
+    f: &Polynomial<Integer>,
+    rng: &mut impl Rng,
+) -> Option<Vec<Polynomial<Integer>>> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "Factorization in Z[x] with short vectors",
+        f = format!("{f:?}"),
+    );
+    let _enter = scope.enter();
+
+    if f.deg() == 1 {
+        return Some(vec![f.clone()]);
+    }
+
+    let b = f.lc();
+    let (p, modular) = PRIMES
+        .into_iter()
+        .filter(|p| b % *p as Integer != 0)
+        .find_map(|p| with_prime!(p, P => modular_factors::<P>(f, rng)).map(|h| (p, h)))
+        .expect("f is squarefree modulo some prime");
+
+    let r = modular.len();
+    let modulus = || {
+        let mignotte = Integer::try_from(mignotte_bound(f)?).ok()?.checked_mul(2)?;
+        let lattice = power_sum_bounds(f)?[POWER_SUMS - 1]
+            .checked_mul(1i128.checked_shl((r + POWER_SUMS) as u32)?)?
+            .checked_mul(recombination_bound(r))?;
+        let bound = mignotte.max(lattice);
+
+        let (mut l, mut pl) = (1, p as Integer);
+        while pl <= bound {
+            pl = pl.checked_mul(p as Integer)?;
+            l += 1;
+        }
+        lifting_fits(pl, f.deg()).then_some((l, pl))
+    };
+    debug!("p = {p}, f ≡ {b} · {modular:?} mod {p}");
+
+    modulus()
+        .and_then(|(l, pl)| {
+            debug!("l = {l}");
+            let lifted = multifactor_hensel_lifting(p, l, f, &modular);
+            factor_recombination(f, &lifted, pl, DEFAULT_DELTA)
+        })
+        .or_else(|| {
+            debug!("falling back to Zassenhaus's algorithm");
+            zassenhaus_factorization(f, rng)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn poly(cs: &[Integer]) -> Polynomial<Integer> {
+        Polynomial::new(cs.to_vec())
+    }
+
+    /// The reduced bases agree up to unimodular transformations.
+    fn same_lattice(f: &[Vec<Integer>], g: &[Vec<Integer>]) -> bool {
+        Matrix::new(f.to_vec()).hermite_normal_form().0
+            == Matrix::new(g.to_vec()).hermite_normal_form().0
+    }
+
+    #[test]
+    fn basis_reduction() {
+        let f = vec![vec![12, 2], vec![13, 4]];
+        let reduction = BasisReduction::run(&f, DEFAULT_DELTA);
+        assert_eq!(reduction.basis, [vec![1, 2], vec![9, -4]]);
+        assert_eq!(
+            reduction.norms,
+            [Rational::from(5), Rational::from(484) / Rational::from(5)]
+        );
+
+        let f = vec![
+            vec![1, 0, 0, 0, 31],
+            vec![0, 1, 0, 0, -47],
+            vec![0, 0, 1, 0, 99],
+            vec![0, 0, 0, 1, 12],
+            vec![5, 7, -3, 8, 200],
+        ];
+        for delta in [
+            DEFAULT_DELTA,
+            Rational {
+                num: 99,
+                denom: 100,
+            },
+        ] {
+            let reduction = BasisReduction::run(&f, delta);
+            let g = &reduction.basis;
+            assert!(same_lattice(&f, g));
+            assert!(reduction.swaps > 0 && reduction.size_reductions > 0);
+
+            let half = Rational { num: 1, denom: 2 };
+            for (i, mu) in reduction.mu.iter().enumerate() {
+                assert!(mu.iter().all(|m| m.abs() <= half));
+                if i > 0 {
+                    let m = mu[i - 1];
+                    assert!(reduction.norms[i] >= (delta - m * m) * reduction.norms[i - 1]);
+                }
+            }
+            // the exact Gram–Schmidt data of the reduced basis
+            assert_eq!(BasisReduction::run(g, delta).norms, reduction.norms);
+        }
+    }
+
+    #[test]
+    fn short_vectors() {
+        // the vectors orthogonal to (31, -47, 99, 12) modulo 1009
+        let f = vec![
+            vec![1, 0, 0, 31],
+            vec![0, 1, 0, -47],
+            vec![0, 0, 1, 99],
+            vec![0, 0, 0, 1009],
+        ];
+        let g = short_vector(&f, DEFAULT_DELTA);
+        assert!(dot(&g, &g) <= 4 * 16);
+        assert_eq!((31 * g[0] - 47 * g[1] + 99 * g[2] - g[3]) % 1009, 0);
+
+        // the minimal polynomial x³ - 2 of 2^(1/3) ≈ 1.259921049894873
+        let alpha = 1.259921049894873f64;
+        let xs = (0..4)
+            .map(|k| Rational {
+                num: (alpha.powi(k) * 1e12).round() as Integer,
+                denom: 1_000_000_000_000,
+            })
+            .collect::<Vec<_>>();
+        let c = integer_relation(&xs, 100_000_000, DEFAULT_DELTA);
+        let sign = c[3].signum();
+        assert_eq!(
+            c.iter().map(|c| c * sign).collect::<Vec<_>>(),
+            [-2, 0, 0, 1]
+        );
+    }
+
+    #[test]
+    fn recombination() {
+        let mut rng = StdRng::seed_from_u64(16);
+
+        // (x² + 1)(x² - 2) ≡ (x - 4)(x + 4)(x - 6)(x + 6) mod 17
+        let f = poly(&[-2, 0, -1, 0, 1]);
+        let modular = [poly(&[-4, 1]), poly(&[4, 1]), poly(&[-6, 1]), poly(&[6, 1])];
+        let pl = (17 as Integer).pow(5);
+        let lifted = multifactor_hensel_lifting(17, 5, &f, &modular);
+        assert_eq!(
+            factor_recombination(&f, &lifted, pl, DEFAULT_DELTA),
+            Some(vec![poly(&[1, 0, 1]), poly(&[-2, 0, 1])])
+        );
+
+        // for l = 1 the lattice row pˡ / D₂ = 17/36 rounds to zero
+        assert_eq!(factor_recombination(&f, &modular, 17, DEFAULT_DELTA), None);
+
+        // Swinnerton-Dyer: the roots ±√2 ± √3 pair up to x² - (5 ± 2√6),
+        // whose first power sums vanish but whose second ones do not
+        let g = poly(&[1, 0, -10, 0, 1]);
+        let modular = [
+            poly(&[-2, 1]),
+            poly(&[2, 1]),
+            poly(&[-11, 1]),
+            poly(&[11, 1]),
+        ];
+        let lifted = multifactor_hensel_lifting(23, 5, &g, &modular);
+        assert_eq!(
+            factor_recombination(&g, &lifted, (23 as Integer).pow(5), DEFAULT_DELTA),
+            Some(vec![g.clone()])
+        );
+
+        // 2²⁰⁰ ≡ 2⁷³ mod 2¹²⁷ - 1 without overflowing
+        let u = poly(&[0, 1 << 100, 1]);
+        assert_eq!(power_sums(&u, 1, Integer::MAX), [-(1 << 100), 1 << 73]);
+
+        // (x - 2)(3x + 1)(x² + x + 1)(x⁴ + 1)
+        let factors = [
+            poly(&[-2, 1]),
+            poly(&[1, 3]),
+            poly(&[1, 1, 1]),
+            poly(&[1, 0, 0, 0, 1]),
+        ];
+        let f = factors.iter().fold(Polynomial::one(), |f, g| f * g.clone());
+        let mut found = factorization_with_short_vectors(&f, &mut rng).unwrap();
+        found.sort_by_key(|g| (g.deg(), g.coef_at(0)));
+        assert_eq!(found, factors);
+        assert_eq!(
+            factorization_with_short_vectors(&g, &mut rng),
+            Some(vec![g])
+        );
+
+        // neither the lattice nor Zassenhaus's lifting fits for x⁶⁴ + x + 1
+        let mut cs = vec![0; 65];
+        (cs[0], cs[1], cs[64]) = (1, 1, 1);
+        assert_eq!(factorization_with_short_vectors(&poly(&cs), &mut rng), None);
+    }
+}
@@ -0,0 +1,386 @@
+//! # Primality testing
+
+use rand::Rng;
+use tracing::debug;
+
+use crate::{
+    ch03::gcd,
+    naturals::{mul_mod, pow_mod},
+    number_theory::natural_jacobi_symbol,
+    Integer, Natural,
+};
+
+/// The smallest prime divisor `p ≤ bound` of `n > 1` with `p² ≤ n` or
+/// `p = n`, by trial division.
+pub fn trial_division(n: Natural, bound: Natural) -> Option<Natural> {
+    let mut t = 2;
+    while t <= bound && t <= n / t {
+        if n.is_multiple_of(t) {
+            return Some(t);
+        }
+        t += 1;
+    }
+    (n > 1 && n <= bound).then_some(n)
+}
+
+/// Whether `n` is prime, by trial division up to `√n`.
+pub fn is_prime_by_trial_division(n: Natural) -> bool {
+    n > 1 && trial_division(n, n) == Some(n)
+}
+
+/// Whether `aⁿ⁻¹ ≡ 1 mod n`, that is `n` is a Fermat probable prime to the
+/// base `a`.
+pub fn is_probable_prime(n: Natural, a: Natural) -> bool {
+    pow_mod(a, n - 1, n) == 1
+}
+
+/// `2, 3` are prime and `0, 1` and the other even numbers are not, which
+/// leaves the odd `n ≥ 5` to the randomized tests.
+fn small_cases(n: Natural) -> Option<bool> {
+    match n {
+        0 | 1 => Some(false),
+        2 | 3 => Some(true),
+        _ if n.is_multiple_of(2) => Some(false),
+        _ => None,
+    }
+}
+
+/// Algorithm 18.1 Fermat test.
+///
+/// - Input: `n ∈ N` and the number of rounds `k`.
+/// - Output: `false` if `n` is composite, `true` if `n` is a Fermat probable
+///   prime to `k` random bases `a ∈ {2, ..., n - 2}`.
+///
+/// Carmichael numbers such as `561 = 3 · 11 · 17` pass every round whose
+/// base is coprime to `n`.
+pub fn fermat_test(n: Natural, k: usize, rng: &mut impl Rng) -> bool {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(18.1) Fermat test",
+        n = n.to_string(),
+        k = k.to_string(),
+    );
+    let _enter = scope.enter();
+
+    if let Some(prime) = small_cases(n) {
+        return prime;
+    }
+
+    (0..k).all(|_| {
+        let a = rng.gen_range(2..n - 1);
+        let prime = is_probable_prime(n, a);
+        debug!("a = {a}: a^(n-1) ≡ 1 is {prime}");
+        prime
+    })
+}
+
+/// Whether the odd `n > 2` is a strong probable prime to the base `a`:
+/// with `n - 1 = 2ˢ d` for odd `d`, either `aᵈ ≡ 1` or `a^(2ⁱ d) ≡ -1 mod n`
+/// for some `0 ≤ i < s`.
+pub fn is_strong_probable_prime(n: Natural, a: Natural) -> bool {
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    let mut b = pow_mod(a, d, n);
+    if b == 1 || b == n - 1 {
+        return true;
+    }
+    for _ in 1..s {
+        b = mul_mod(b, b, n);
+        if b == n - 1 {
+            return true;
+        }
+    }
+    false
+}
+
+/// Algorithm 18.5 Strong pseudoprimality test (the Miller–Rabin test).
+///
+/// - Input: `n ∈ N` and the number of rounds `k`.
+/// - Output: `false` if `n` is composite, `true` if `n` is a strong probable
+///   prime to `k` random bases, where a composite `n` passes a round with
+///   probability at most `1/4`.
+pub fn miller_rabin(n: Natural, k: usize, rng: &mut impl Rng) -> bool {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(18.5) Strong pseudoprimality test",
+        n = n.to_string(),
+        k = k.to_string(),
+    );
+    let _enter = scope.enter();
+
+    if let Some(prime) = small_cases(n) {
+        return prime;
+    }
+
+    (0..k).all(|_| {
+        let a = rng.gen_range(2..n - 1);
+        let prime = is_strong_probable_prime(n, a);
+        debug!("a = {a}: strong probable prime is {prime}");
+        prime
+    })
+}
+
+/// The first 13 primes, which are a witness set for all
+/// `n <` [`WITNESS_BOUND`] (Sorenson and Webster).
+const WITNESSES: [Natural; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+/// The bound `3317044064679887385961981 ≈ 2⁸¹` below which [`is_prime`] is
+/// deterministic.
+pub const WITNESS_BOUND: Natural = 3317044064679887385961981;
+
+/// The primes below 100, used as bases beyond [`WITNESS_BOUND`].
+const EXTRA_BASES: [Natural; 12] = [43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97];
+
+/// Whether `n` is prime, which is proven for `n <` [`WITNESS_BOUND`] but
+/// only a probable-prime test above it.
+///
+/// After trial division by the small primes, `n` is tested for being a
+/// strong probable prime to the bases `2, 3, ..., 41`, which proves
+/// primality for `n < 3.3 · 10²⁴`. For larger `n`, where no witness set is
+/// known, the primes up to `97` are used as further fixed bases, so a
+/// composite passing them would have to be a strong pseudoprime to all 25
+/// of them. No such composite is known, but none is ruled out either, so
+/// a primality certificate is needed for a proof there.
+pub fn is_prime(n: Natural) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for p in WITNESSES.into_iter().chain(EXTRA_BASES) {
+        if n == p {
+            return true;
+        }
+        if n.is_multiple_of(p) {
+            return false;
+        }
+    }
+
+    let bases = if n < WITNESS_BOUND {
+        &WITNESSES[..]
+    } else {
+        &[WITNESSES.as_slice(), EXTRA_BASES.as_slice()].concat()
+    };
+    bases.iter().all(|&a| is_strong_probable_prime(n, a))
+}
+
+/// The Solovay–Strassen test.
+///
+/// - Input: `n ∈ N` and the number of rounds `k`.
+/// - Output: `false` if `n` is composite, `true` if
+///   `a^((n-1)/2) ≡ (a/n) ≢ 0 mod n` for `k` random bases `a`, which holds
+///   for at most half of the bases of a composite `n`.
+pub fn solovay_strassen(n: Natural, k: usize, rng: &mut impl Rng) -> bool {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "Solovay–Strassen test",
+        n = n.to_string(),
+        k = k.to_string(),
+    );
+    let _enter = scope.enter();
+
+    if let Some(prime) = small_cases(n) {
+        return prime;
+    }
+
+    (0..k).all(|_| {
+        let a = rng.gen_range(2..n - 1);
+        let jacobi = match natural_jacobi_symbol(a, n) {
+            0 => return false,
+            1 => 1,
+            _ => n - 1,
+        };
+        let euler = pow_mod(a, (n - 1) / 2, n);
+        debug!("a = {a}: a^((n-1)/2) = {euler}, (a/n) = {jacobi}");
+        euler == jacobi
+    })
+}
+
+/// `⌊n^(1/b)⌋`, by bisection.
+pub(crate) fn integer_root(n: Natural, b: u32) -> Natural {
+    let (mut low, mut high): (Natural, Natural) = (0, 1 << (128 / b + 1).min(127));
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        match mid.checked_pow(b) {
+            Some(power) if power <= n => low = mid,
+            _ => high = mid - 1,
+        }
+    }
+    low
+}
+
+/// `fg mod (xʳ - 1, n)` for coefficient vectors of length `r`.
+fn mul_cyclic(f: &[Natural], g: &[Natural], n: Natural) -> Vec<Natural> {
+    let r = f.len();
+    let mut h = vec![0; r];
+    for (i, a) in f.iter().enumerate().filter(|(_, a)| **a != 0) {
+        for (j, b) in g.iter().enumerate() {
+            h[(i + j) % r] = (h[(i + j) % r] + a * b) % n;
+        }
+    }
+    h
+}
+
+/// The AKS test of Agrawal, Kayal and Saxena.
+///
+/// - Input: `1 < n < 2³²`.
+/// - Output: whether `n` is prime.
+///
+/// After excluding perfect powers, let `r` be the least with
+/// `ord_r(n) > log₂² n`. Then `n` is prime if and only if it has no factor
+/// `≤ r` and `(x + a)ⁿ ≡ xⁿ + a mod (xʳ - 1, n)` for all
+/// `1 ≤ a ≤ ⌊√φ(r) log₂ n⌋`. This runs in polynomial time but is far slower
+/// than [`is_prime`], and serves as a reference for small inputs.
+pub fn aks(n: Natural) -> bool {
+    let scope = tracing::span!(tracing::Level::DEBUG, "AKS test", n = n.to_string());
+    let _enter = scope.enter();
+
+    assert!(1 < n && n < 1 << 32, "the AKS test is only for 1 < n < 2³²");
+
+    // 1.
+    let log = (Natural::BITS - n.leading_zeros()) as Natural;
+    if (2..log as u32).any(|b| integer_root(n, b).pow(b) == n) {
+        debug!("perfect power");
+        return false;
+    }
+
+    // 2.
+    let order = |r: Natural| {
+        let mut k = 1;
+        let mut power = n % r;
+        while power != 1 {
+            power = power * (n % r) % r;
+            k += 1;
+        }
+        k
+    };
+    let coprime = |a: Natural, b: Natural| gcd(&(a as Integer), &(b as Integer)) == 1;
+    let r = (2..)
+        .find(|&r| coprime(r, n) && order(r) > log * log)
+        .expect("some r ≤ max(3, log₂⁵ n) has a large enough order");
+    debug!("r = {r}");
+
+    // 3.
+    if let Some(p) = trial_division(n, r.min(n - 1)) {
+        debug!("{p} divides n");
+        return false;
+    }
+
+    // 4.
+    if n <= r {
+        return true;
+    }
+
+    // 5.
+    let phi = (1..r).filter(|&k| coprime(k, r)).count() as Natural;
+    // ⌊√(φ(r) log²)⌋ ≥ ⌊√φ(r) log₂ n⌋, as log is the bit length of n
+    let limit = integer_root(phi * log * log, 2);
+    let r = r as usize;
+    for a in 1..=limit {
+        let mut base = vec![0; r];
+        base[0] = a % n;
+        base[1] = 1;
+        let mut power = vec![0; r];
+        power[0] = 1;
+        let mut e = n;
+        while e > 0 {
+            if e & 1 == 1 {
+                power = mul_cyclic(&power, &base, n);
+            }
+            e >>= 1;
+            if e > 0 {
+                base = mul_cyclic(&base, &base, n);
+            }
+        }
+
+        let mut expected = vec![0; r];
+        expected[0] = a % n;
+        expected[n as usize % r] = (expected[n as usize % r] + 1) % n;
+        if power != expected {
+            debug!("(x + {a})^n ≢ x^n + {a}");
+            return false;
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::finite::PRIMES;
+
+    use super::*;
+
+    fn sieve(n: usize) -> Vec<bool> {
+        let mut prime = vec![true; n];
+        prime[0] = false;
+        prime[1] = false;
+        for i in 2..n {
+            if prime[i] {
+                for j in (i * i..n).step_by(i) {
+                    prime[j] = false;
+                }
+            }
+        }
+        prime
+    }
+
+    #[test]
+    fn small_numbers() {
+        let mut rng = StdRng::seed_from_u64(18);
+        for (n, prime) in sieve(2000).into_iter().enumerate() {
+            let n = n as Natural;
+            assert_eq!(is_prime_by_trial_division(n), prime, "{n}");
+            assert_eq!(is_prime(n), prime, "{n}");
+            assert_eq!(miller_rabin(n, 10, &mut rng), prime, "{n}");
+            assert_eq!(solovay_strassen(n, 20, &mut rng), prime, "{n}");
+            if n > 1 && n < 300 {
+                assert_eq!(aks(n), prime, "{n}");
+            }
+        }
+        assert!(aks(1009));
+        assert!(!aks(1001));
+        assert!(!aks(3 * 3 * 3 * 3 * 3 * 3));
+    }
+
+    #[test]
+    fn pseudoprimes() {
+        let mut rng = StdRng::seed_from_u64(18);
+
+        // Carmichael numbers fool the Fermat test for all coprime bases
+        for n in [561, 1105, 1729, 2465, 2821, 6601, 8911] {
+            assert!((2..n)
+                .filter(|&a| gcd(&(a as Integer), &(n as Integer)) == 1)
+                .all(|a| is_probable_prime(n, a)));
+            assert!(!miller_rabin(n, 10, &mut rng));
+            assert!(!solovay_strassen(n, 20, &mut rng));
+        }
+        assert!(fermat_test(PRIMES[30], 10, &mut rng));
+
+        // the least strong pseudoprime to the bases 2, 3, ..., 23
+        let n = 3825123056546413051;
+        assert!(WITNESSES[..9]
+            .iter()
+            .all(|&a| is_strong_probable_prime(n, a)));
+        assert!(!is_prime(n));
+        assert_eq!(trial_division(n, 200000), Some(149491));
+    }
+
+    #[test]
+    fn large_numbers() {
+        let mut rng = StdRng::seed_from_u64(18);
+
+        // the field sizes of `Finite<P>`
+        for p in PRIMES {
+            assert!(is_prime(p), "{p}");
+            assert!(miller_rabin(p, 10, &mut rng));
+        }
+        // 2¹²⁷ - 1, 2¹²⁸ - 159 and a product of two 61-bit primes
+        assert!(is_prime((1 << 127) - 1));
+        assert!(is_prime(Natural::MAX - 158));
+        assert!(solovay_strassen(Natural::MAX - 158, 10, &mut rng));
+        assert!(!is_prime(Natural::MAX));
+        assert!(!is_prime(PRIMES[79] * PRIMES[80]));
+        assert!(!miller_rabin(PRIMES[79] * PRIMES[80], 5, &mut rng));
+    }
+}
@@ -0,0 +1,409 @@
+//! # Factoring integers
+
+use std::collections::BTreeMap;
+
+use rand::Rng;
+use tracing::debug;
+
+use crate::{
+    ch18::{integer_root, is_prime, trial_division},
+    matrix::Matrix,
+    naturals::{gcd, mul_mod, pow_mod},
+    number_theory::natural_jacobi_symbol,
+    Finite, Group, Natural,
+};
+
+/// The prime factors `(p, e)` of `pᵉ`, in increasing order of `p`.
+pub type IntegerFactorization = Vec<(Natural, Natural)>;
+
+/// Algorithm 19.1 Trial division.
+///
+/// - Input: `n ≥ 1` and a bound `b`.
+/// - Output: the prime factors `p ≤ b` of `n` with their multiplicities,
+///   and the cofactor `m` that has no prime factors `≤ b`, where `m` is
+///   prime or `1` if `m < b²`.
+pub fn trial_division_factorization(mut n: Natural, b: Natural) -> (IntegerFactorization, Natural) {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(19.1) Trial division",
+        n = n.to_string(),
+        b = b.to_string(),
+    );
+    let _enter = scope.enter();
+
+    let mut factors = vec![];
+    while let Some(p) = trial_division(n, b) {
+        let mut e = 0;
+        while n.is_multiple_of(p) {
+            n /= p;
+            e += 1;
+        }
+        debug!("{p}^{e}");
+        factors.push((p, e));
+    }
+
+    (factors, n)
+}
+
+/// `x² + c mod n`
+fn pseudorandom(x: Natural, c: Natural, n: Natural) -> Natural {
+    let y = mul_mod(x, x, n);
+    if y >= n - c {
+        y - (n - c)
+    } else {
+        y + c
+    }
+}
+
+/// The number of steps whose differences are multiplied together before
+/// taking a gcd with `n` in [`pollards_rho`].
+const BATCH: usize = 64;
+
+/// The number of random `c` tried by [`pollards_rho`].
+const RHO_ATTEMPTS: usize = 32;
+
+/// Algorithm 19.8 Pollard's ρ method, with Brent's cycle detection.
+///
+/// - Input: composite `n`.
+/// - Output: a nontrivial divisor of `n`, or `None` if all attempts failed.
+///
+/// The sequence `xᵢ₊₁ = xᵢ² + c mod n` is eventually periodic modulo each
+/// prime `p | n`, after about `√p` steps, and then `gcd(xᵢ - xⱼ, n)` reveals
+/// `p`. Instead of Floyd's `x₂ᵢ` against `xᵢ`, Brent compares `y` with the
+/// last `x` saved at a power of two, which saves a third of the evaluations,
+/// and the differences are batched into one product per gcd.
+pub fn pollards_rho(n: Natural, rng: &mut impl Rng) -> Option<Natural> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "(19.8) Pollard's rho",
+        n = n.to_string(),
+    );
+    let _enter = scope.enter();
+
+    if n.is_multiple_of(2) {
+        return Some(2);
+    }
+
+    for _ in 0..RHO_ATTEMPTS {
+        let c = rng.gen_range(1..n - 1);
+        let (mut y, mut x, mut saved) = (rng.gen_range(0..n), 0, 0);
+        let (mut g, mut q, mut r) = (1, 1, 1);
+
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = pseudorandom(y, c, n);
+            }
+            let mut k = 0;
+            while k < r && g == 1 {
+                saved = y;
+                for _ in 0..BATCH.min(r - k) {
+                    y = pseudorandom(y, c, n);
+                    q = mul_mod(q, x.abs_diff(y), n);
+                }
+                g = gcd(q, n);
+                k += BATCH;
+            }
+            r *= 2;
+        }
+        debug!("c = {c}: gcd = {g} after a cycle length bound of {r}");
+
+        // the batch overshot, so retrace its steps one at a time
+        if g == n {
+            loop {
+                saved = pseudorandom(saved, c, n);
+                g = gcd(x.abs_diff(saved), n);
+                if g > 1 {
+                    break;
+                }
+            }
+        }
+        if g != n {
+            debug!("{g} divides {n}");
+            return Some(g);
+        }
+    }
+
+    None
+}
+
+/// Pollard's `p - 1` method.
+///
+/// - Input: composite `n` and a smoothness bound `b`.
+/// - Output: a nontrivial divisor of `n`, or `None`.
+///
+/// With `a = 2^(b!) mod n`, every prime `p | n` for which `p - 1` divides
+/// `b!`, in particular when `p - 1` is `b`-powersmooth, divides `a - 1` by
+/// Fermat's little theorem. This finds such `p` as `gcd(a - 1, n)`, unless
+/// all prime factors of `n` are found at once.
+pub fn pollards_p_minus_1(n: Natural, b: Natural) -> Option<Natural> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "Pollard's p - 1",
+        n = n.to_string(),
+        b = b.to_string(),
+    );
+    let _enter = scope.enter();
+
+    let mut a = 2 % n;
+    for k in 2..=b {
+        a = pow_mod(a, k, n);
+        let g = gcd(if a == 0 { n - 1 } else { a - 1 }, n);
+        if g == n {
+            debug!("all prime factors found at k = {k}");
+            return None;
+        }
+        if g > 1 {
+            debug!("{g} divides {n} at k = {k}");
+            return Some(g);
+        }
+    }
+
+    None
+}
+
+/// A basic quadratic sieve.
+///
+/// - Input: odd composite `n` which is not a perfect power, a bound `b` for
+///   the factor base and the length `m` of the sieve interval.
+/// - Output: a nontrivial divisor of `n`, or `None` if the relations found
+///   do not split `n` or `Q(x)` exceeds the range of [`Natural`].
+///
+/// The factor base consists of the primes `p ≤ b` for which `n` is a square
+/// modulo `p`, since only those divide some `Q(x) = x² - n`. For `x` in
+/// `⌈√n⌉, ..., ⌈√n⌉ + m - 1` the values `Q(x)` are sieved by dividing out
+/// each `p` at the `x ≡ ±√n mod p`, and the `Q(x)` that are left at `1` were
+/// smooth, and only those are factored over the base. A subset of these with
+/// all exponents even, given by the kernel of the exponent matrix over `F₂`,
+/// yields `X² ≡ Y² mod n` for
+/// `X = ∏ x` and `Y = √∏ Q(x)`, and then `gcd(X - Y, n)` may split `n`.
+pub fn quadratic_sieve(n: Natural, b: Natural, m: Natural) -> Option<Natural> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "Quadratic sieve",
+        n = n.to_string(),
+        b = b.to_string(),
+        m = m.to_string(),
+    );
+    let _enter = scope.enter();
+
+    // 1.
+    let mut base = vec![2];
+    for p in (3..=b).step_by(2).filter(|&p| is_prime(p)) {
+        if n.is_multiple_of(p) {
+            return (p < n).then_some(p);
+        }
+        if natural_jacobi_symbol(n, p) == 1 {
+            base.push(p);
+        }
+    }
+    debug!("factor base {base:?}");
+
+    // 2.
+    let s = integer_root(n, 2) + 1;
+    if (s + m).checked_mul(s + m).is_none() {
+        debug!("Q(x) exceeds the range of Natural");
+        return None;
+    }
+    let mut values = (0..m).map(|i| (s + i) * (s + i) - n).collect::<Vec<_>>();
+
+    // 3.
+    for &p in &base {
+        for r in (0..p).filter(|r| r * r % p == n % p) {
+            let start = (r + p - s % p) % p;
+            for i in (start..m).step_by(p as usize) {
+                let i = i as usize;
+                while values[i].is_multiple_of(p) {
+                    values[i] /= p;
+                }
+            }
+        }
+    }
+
+    // 4.
+    let exponents = |x: Natural| {
+        let mut q = x * x - n;
+        base.iter()
+            .map(|&p| {
+                let mut e = 0u32;
+                while q.is_multiple_of(p) {
+                    q /= p;
+                    e += 1;
+                }
+                e
+            })
+            .collect::<Vec<_>>()
+    };
+    let relations = (0..m as usize)
+        .filter(|&i| values[i] == 1)
+        .map(|i| (s + i as Natural, exponents(s + i as Natural)))
+        .collect::<Vec<_>>();
+    debug!(
+        "{} smooth values for {} primes",
+        relations.len(),
+        base.len()
+    );
+
+    // 5.
+    let parities = Matrix::from_fn(base.len(), relations.len(), |j, i| {
+        Finite::<2>::from((relations[i].1[j] % 2) as Natural)
+    });
+    for dependency in parities.kernel() {
+        let subset = (0..relations.len())
+            .filter(|&i| !dependency[i].is_zero())
+            .collect::<Vec<_>>();
+
+        // 6.
+        let x = subset.iter().fold(1, |x, &i| mul_mod(x, relations[i].0, n));
+        let y = base.iter().enumerate().fold(1, |y, (j, &p)| {
+            let e: Natural = subset.iter().map(|&i| relations[i].1[j] as Natural).sum();
+            mul_mod(y, pow_mod(p, e / 2, n), n)
+        });
+        let g = gcd(x.abs_diff(y), n);
+        debug!("X = {x}, Y = {y} from {subset:?}: gcd(X - Y, n) = {g}");
+        if 1 < g && g < n {
+            return Some(g);
+        }
+    }
+
+    None
+}
+
+/// The bound for trial division in [`factor`].
+const TRIAL_BOUND: Natural = 1000;
+
+/// The prime factorization of `n ≥ 1`.
+///
+/// The primes below [`TRIAL_BOUND`] are removed by
+/// [`trial_division_factorization`], and the cofactor is split with
+/// [`pollards_rho`] until all parts pass [`is_prime`], falling back to
+/// [`pollards_p_minus_1`] and the [`quadratic_sieve`] if `ρ` fails.
+pub fn factor(n: Natural, rng: &mut impl Rng) -> IntegerFactorization {
+    let scope = tracing::span!(tracing::Level::DEBUG, "Factorization", n = n.to_string());
+    let _enter = scope.enter();
+
+    assert!(n > 0, "cannot factor zero");
+
+    let (small, cofactor) = trial_division_factorization(n, TRIAL_BOUND);
+    let mut factors: BTreeMap<Natural, Natural> = small.into_iter().collect();
+
+    let mut parts = vec![(cofactor, 1)];
+    while let Some((m, e)) = parts.pop() {
+        if m == 1 {
+            continue;
+        }
+        if is_prime(m) {
+            debug!("{m} is prime");
+            *factors.entry(m).or_default() += e;
+            continue;
+        }
+        if let Some((r, k)) = (2..Natural::BITS - m.leading_zeros())
+            .rev()
+            .map(|k| (integer_root(m, k), k))
+            .find(|(r, k)| r.pow(*k) == m)
+        {
+            debug!("{m} = {r}^{k}");
+            parts.push((r, e * k as Natural));
+            continue;
+        }
+
+        let d = pollards_rho(m, rng)
+            .or_else(|| pollards_p_minus_1(m, TRIAL_BOUND))
+            .or_else(|| quadratic_sieve(m, TRIAL_BOUND, 100 * TRIAL_BOUND))
+            .unwrap_or_else(|| panic!("failed to split {m}"));
+        debug!("{m} = {d} · {}", m / d);
+        parts.push((d, e));
+        parts.push((m / d, e));
+    }
+
+    factors.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::finite::PRIMES;
+
+    use super::*;
+
+    fn expand(factors: &IntegerFactorization) -> Natural {
+        factors.iter().map(|(p, e)| p.pow(*e as u32)).product()
+    }
+
+    #[test]
+    fn trial_division() {
+        let (factors, cofactor) = trial_division_factorization(2 * 2 * 2 * 3 * 97 * 1009, 100);
+        assert_eq!(factors, [(2, 3), (3, 1), (97, 1)]);
+        assert_eq!(cofactor, 1009);
+
+        assert_eq!(trial_division_factorization(1, 100), (vec![], 1));
+    }
+
+    #[test]
+    fn pollard() {
+        let mut rng = StdRng::seed_from_u64(19);
+
+        let (p, q) = (PRIMES[45], PRIMES[47]);
+        let d = pollards_rho(p * q, &mut rng).unwrap();
+        assert!(d == p || d == q);
+        let d = pollards_rho(1009 * 1009, &mut rng).unwrap();
+        assert_eq!(d, 1009);
+
+        // 65536 = 2¹⁶ is smooth but 1000002 = 2 · 3 · 166667 is not
+        assert_eq!(pollards_p_minus_1(65537 * 1000003, 20), Some(65537));
+        assert_eq!(pollards_p_minus_1(PRIMES[60] * PRIMES[62], 20), None);
+
+        // n > 2¹²⁷ with the smooth p - 1 = 2⁴⁷ · 3⁷ · 5 · 7
+        let p = (1 << 47) * 3u128.pow(7) * 5 * 7 + 1;
+        assert_eq!(pollards_p_minus_1(p * ((1 << 64) + 13), 100), Some(p));
+    }
+
+    #[test]
+    fn quadratic_sieves() {
+        // 87463 = 149 · 587 with the factor base 2, 3, 13, 17, 19, 29
+        let d = quadratic_sieve(87463, 30, 200).unwrap();
+        assert!(d == 149 || d == 587);
+
+        let n = 1000003 * 999983;
+        let d = quadratic_sieve(n, 500, 20000).unwrap();
+        assert!(d == 1000003 || d == 999983);
+
+        // Q(x) does not fit for n = (2⁶⁴ + 13)(2⁶⁴ - 59)
+        assert_eq!(
+            quadratic_sieve(((1 << 64) + 13) * (u64::MAX as Natural - 58), 100, 1000),
+            None
+        );
+    }
+
+    #[test]
+    fn factorizations() {
+        let mut rng = StdRng::seed_from_u64(19);
+
+        let n = 2 * 2 * 2 * 2 * 2 * 3 * 3 * PRIMES[40] * PRIMES[40] * PRIMES[55];
+        let factors = factor(n, &mut rng);
+        assert_eq!(factors, [(2, 5), (3, 2), (PRIMES[40], 2), (PRIMES[55], 1)]);
+
+        // the Fermat numbers F₀, ..., F₄ and the factors of F₅ and F₆
+        let factors = factor(Natural::MAX, &mut rng);
+        assert_eq!(
+            factors,
+            [
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65537, 1),
+                (274177, 1),
+                (6700417, 1),
+                (67280421310721, 1),
+            ]
+        );
+        assert_eq!(expand(&factors), Natural::MAX);
+
+        assert_eq!(factor(1, &mut rng), []);
+        assert_eq!(factor(PRIMES[80], &mut rng), [(PRIMES[80], 1)]);
+        assert_eq!(factor(1031 * 1031 * 1031, &mut rng), [(1031, 3)]);
+    }
+}
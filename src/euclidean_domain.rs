@@ -5,4 +5,21 @@ pub trait EuclideanDomain:
     Domain + std::ops::Rem<Output = Self> + std::ops::Div<Output = Self>
 {
     fn d(&self) -> Option<Natural>;
+    /// The leading unit `lu(a)` of `a`, such that `a = lu(a) · normal(a)`.
+    /// By convention `lu(0) = 1`.
+    fn lu(&self) -> Self;
+    /// The normal form `normal(a) = a / lu(a)`, e.g. the absolute value of
+    /// an integer or the monic associate of a polynomial.
+    fn normal(&self) -> Self {
+        self.clone()
+            * self
+                .lu()
+                .multiplicative_inverse()
+                .expect("the leading unit is a unit")
+    }
+    /// The canonical representative of `self mod m`. By default this is the
+    /// remainder `self rem m`, which integers make nonnegative.
+    fn rem_canonical(&self, m: &Self) -> Self {
+        self.clone() % m.clone()
+    }
 }
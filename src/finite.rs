@@ -1,4 +1,5 @@
 use crate::{
+    ch03::extended_euclidean_algorithm,
     domain::Domain,
     euclidean_domain::EuclideanDomain,
     field::Field,
@@ -20,6 +21,20 @@ impl<const N: Natural> std::fmt::Debug for Finite<N> {
         self.val.fmt(f)
     }
 }
+impl<const N: Natural> Finite<N> {
+    /// The representative of `self` in `{0, ..., N - 1}`.
+    pub fn value(self) -> Natural {
+        self.val
+    }
+    /// The symmetric representative of `self` in `{-⌈N/2⌉ + 1, ..., ⌊N/2⌋}`.
+    pub fn symmetric(self) -> Integer {
+        if self.val > N / 2 {
+            -((N - self.val) as Integer)
+        } else {
+            self.val as Integer
+        }
+    }
+}
 impl<const N: Natural> From<Natural> for Finite<N> {
     fn from(i: Natural) -> Self {
         Finite { val: i % N }
@@ -82,16 +97,13 @@ impl<const N: Natural> Identity<Multiplication> for Finite<N> {
 }
 impl<const N: Natural> Ring for Finite<N> {
     fn multiplicative_inverse(&self) -> Option<Self> {
-        (1..N)
-            .find(|x| (x * self.val) % N == 1)
-            .map(|x| Finite { val: x })
-    }
+        let eea = extended_euclidean_algorithm(&(self.val as Integer), &(N as Integer));
 
-    fn pow(&self, pow: crate::Natural) -> Self
-    where
-        Self: Clone,
-    {
-        self.val.pow(Natural::from(pow) as u32).into()
+        match eea.gcd() {
+            1 => Some(eea.s[eea.s.len() - 2].into()),
+            -1 => Some((-eea.s[eea.s.len() - 2]).into()),
+            _ => None,
+        }
     }
 }
 
@@ -99,6 +111,10 @@ impl<const N: Natural> std::ops::Div for Finite<N> {
     type Output = Self;
 
     fn div(self, rhs: Self) -> Self::Output {
+        if let Some(inv) = rhs.multiplicative_inverse() {
+            return self * inv;
+        }
+
         for i in 0..N {
             let i = Finite::from(i);
             if self == rhs * i {
@@ -128,3 +144,151 @@ pub mod alias {
         F60: 60, F61: 61, F62: 62, F63: 63, F64: 64, F65: 65, F66: 66, F67: 67, F68: 68, F69: 69,
     );
 }
+
+/// The primes which algorithms choosing their moduli at runtime can
+/// instantiate `Finite` with through [`with_prime!`]: all primes below 100,
+/// followed by the least prime above `2^k` for `k = 7, ..., 62`.
+pub const PRIMES: [Natural; 81] = [
+    2,
+    3,
+    5,
+    7,
+    11,
+    13,
+    17,
+    19,
+    23,
+    29,
+    31,
+    37,
+    41,
+    43,
+    47,
+    53,
+    59,
+    61,
+    67,
+    71,
+    73,
+    79,
+    83,
+    89,
+    97,
+    131,
+    257,
+    521,
+    1031,
+    2053,
+    4099,
+    8209,
+    16411,
+    32771,
+    65537,
+    131101,
+    262147,
+    524309,
+    1048583,
+    2097169,
+    4194319,
+    8388617,
+    16777259,
+    33554467,
+    67108879,
+    134217757,
+    268435459,
+    536870923,
+    1073741827,
+    2147483659,
+    4294967311,
+    8589934609,
+    17179869209,
+    34359738421,
+    68719476767,
+    137438953481,
+    274877906951,
+    549755813911,
+    1099511627791,
+    2199023255579,
+    4398046511119,
+    8796093022237,
+    17592186044423,
+    35184372088891,
+    70368744177679,
+    140737488355333,
+    281474976710677,
+    562949953421381,
+    1125899906842679,
+    2251799813685269,
+    4503599627370517,
+    9007199254740997,
+    18014398509482143,
+    36028797018963971,
+    72057594037928017,
+    144115188075855881,
+    288230376151711813,
+    576460752303423619,
+    1152921504606847009,
+    2305843009213693967,
+    4611686018427388039,
+];
+
+/// Evaluates `$body` with the const `$P` bound to the runtime prime `$p`,
+/// which must be one of [`PRIMES`], such that `Finite<$P>` can be used in
+/// `$body`.
+macro_rules! with_prime {
+    ($p:expr, $P:ident => $body:expr) => {
+        with_prime!(@arms $p, $P => $body;
+            2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83,
+            89, 97, 131, 257, 521, 1031, 2053, 4099, 8209, 16411, 32771, 65537, 131101, 262147,
+            524309, 1048583, 2097169, 4194319, 8388617, 16777259, 33554467, 67108879, 134217757,
+            268435459, 536870923, 1073741827, 2147483659, 4294967311, 8589934609, 17179869209,
+            34359738421, 68719476767, 137438953481, 274877906951, 549755813911, 1099511627791,
+            2199023255579, 4398046511119, 8796093022237, 17592186044423, 35184372088891,
+            70368744177679, 140737488355333, 281474976710677, 562949953421381, 1125899906842679,
+            2251799813685269, 4503599627370517, 9007199254740997, 18014398509482143,
+            36028797018963971, 72057594037928017, 144115188075855881, 288230376151711813,
+            576460752303423619, 1152921504606847009, 2305843009213693967, 4611686018427388039,
+        )
+    };
+    (@arms $p:expr, $P:ident => $body:expr; $($prime:literal,)*) => {
+        match $p {
+            $($prime => {
+                const $P: $crate::Natural = $prime;
+                $body
+            })*
+            p => panic!("{p} is not one of the primes in `finite::PRIMES`"),
+        }
+    };
+}
+pub(crate) use with_prime;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_prime_dispatches() {
+        for p in PRIMES {
+            assert_eq!(
+                with_prime!(p, P => Finite::<P>::from(-1i128).value() + 1),
+                p
+            );
+        }
+    }
+
+    #[test]
+    fn inverses() {
+        type R = Finite<2147483659>;
+
+        for x in [1, 2, 3, 12345, 2147483658i128] {
+            let x = R::from(x);
+            assert!((x * x.multiplicative_inverse().unwrap()).is_one());
+            assert_eq!(R::one() / x, x.multiplicative_inverse().unwrap());
+        }
+        assert_eq!(Finite::<6>::from(3i128).multiplicative_inverse(), None);
+        assert_eq!(
+            Finite::<6>::from(2i128) / Finite::from(2i128),
+            Finite::from(1i128)
+        );
+    }
+}
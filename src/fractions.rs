@@ -0,0 +1,358 @@
+//! # Fields of fractions
+
+use itertools::Itertools;
+
+use crate::{
+    ch03::{extended_euclidean_algorithm, gcd},
+    euclidean_domain::EuclideanDomain,
+    field::Field,
+    group::AbelianGroup,
+    identity::{Addition, Identity, Multiplication},
+    Group, Natural, Polynomial, Ring,
+};
+
+/// An element `num/denom` of the field of fractions of the Euclidean domain
+/// `D`.
+///
+/// Fractions built through [`Fraction::new`] and the arithmetic operations are
+/// kept normalized: `gcd(num, denom) = 1` and `denom` is in normal form, e.g.
+/// positive for integers and monic for polynomials.
+#[derive(Clone)]
+pub struct Fraction<D> {
+    pub num: D,
+    pub denom: D,
+}
+
+/// The field of rational functions `F(x)`, that is the field of fractions of
+/// `F[x]`.
+pub type RationalFunction<F> = Fraction<Polynomial<F>>;
+
+impl<D: EuclideanDomain> Fraction<D> {
+    pub fn new(num: D, denom: D) -> Self {
+        assert!(!denom.is_zero(), "the denominator must be nonzero");
+
+        Fraction { num, denom }.normalized()
+    }
+    pub fn normalized(self) -> Self {
+        if self.num.is_zero() {
+            return Self::zero();
+        }
+
+        let g = gcd(&self.num, &self.denom);
+        let num = self.num / g.clone();
+        let denom = self.denom / g;
+        let u = denom
+            .lu()
+            .multiplicative_inverse()
+            .expect("the leading unit is a unit");
+
+        Fraction {
+            num: num * u.clone(),
+            denom: denom * u,
+        }
+    }
+}
+
+impl<D: EuclideanDomain> From<D> for Fraction<D> {
+    fn from(num: D) -> Self {
+        Fraction {
+            num,
+            denom: D::one(),
+        }
+    }
+}
+
+impl<D: EuclideanDomain> PartialEq for Fraction<D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.num.clone() * other.denom.clone() == other.num.clone() * self.denom.clone()
+    }
+}
+
+impl<D: EuclideanDomain> std::fmt::Debug for Fraction<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn parenthesized(x: &impl std::fmt::Debug) -> String {
+            let s = format!("{x:?}");
+            if s.contains(' ') {
+                format!("({s})")
+            } else {
+                s
+            }
+        }
+
+        if self.denom.is_one() {
+            write!(f, "{:?}", self.num)
+        } else {
+            write!(
+                f,
+                "{}/{}",
+                parenthesized(&self.num),
+                parenthesized(&self.denom)
+            )
+        }
+    }
+}
+
+impl<D: EuclideanDomain> std::ops::Add for Fraction<D> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Fraction::new(
+            self.num * rhs.denom.clone() + rhs.num * self.denom.clone(),
+            self.denom * rhs.denom,
+        )
+    }
+}
+impl<D: EuclideanDomain> std::ops::Neg for Fraction<D> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Fraction {
+            num: -self.num,
+            denom: self.denom,
+        }
+    }
+}
+impl<D: EuclideanDomain> std::ops::Sub for Fraction<D> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+impl<D: EuclideanDomain> std::ops::Mul for Fraction<D> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Fraction::new(self.num * rhs.num, self.denom * rhs.denom)
+    }
+}
+impl<D: EuclideanDomain> std::ops::Div for Fraction<D> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        Fraction::new(self.num * rhs.denom, self.denom * rhs.num)
+    }
+}
+
+impl<D: EuclideanDomain> Identity<Addition> for Fraction<D> {
+    fn identity() -> Self {
+        Fraction {
+            num: D::zero(),
+            denom: D::one(),
+        }
+    }
+}
+impl<D: EuclideanDomain> Group for Fraction<D> {}
+impl<D: EuclideanDomain> AbelianGroup for Fraction<D> {}
+impl<D: EuclideanDomain> Identity<Multiplication> for Fraction<D> {
+    fn identity() -> Self {
+        Fraction {
+            num: D::one(),
+            denom: D::one(),
+        }
+    }
+}
+impl<D: EuclideanDomain> Ring for Fraction<D> {
+    fn multiplicative_inverse(&self) -> Option<Self> {
+        if self.num.is_zero() {
+            None
+        } else {
+            Some(Fraction::new(self.denom.clone(), self.num.clone()))
+        }
+    }
+}
+impl<D: EuclideanDomain> Field for Fraction<D> {}
+
+impl<F: Field> RationalFunction<F> {
+    /// Evaluates `num(u)/denom(u)`, or `None` if `u` is a pole.
+    pub fn evaluate_at(&self, u: impl Into<F>) -> Option<F> {
+        let u = u.into();
+        let denom = self.denom.evaluate_at(u.clone());
+
+        if denom.is_zero() {
+            None
+        } else {
+            Some(self.num.evaluate_at(u) / denom)
+        }
+    }
+
+    /// Section 5.11 Partial fraction decomposition.
+    ///
+    /// Given the factorization `denom = g₁^e₁ ··· gᵣ^eᵣ` into pairwise coprime
+    /// monic nonconstant polynomials `gᵢ`, computes
+    ///
+    /// ```text
+    /// num/denom = q + Σᵢ Σⱼ cᵢⱼ/gᵢ^j
+    /// ```
+    ///
+    /// with `deg cᵢⱼ < deg gᵢ`. Returns `None` if `factors` is not such a
+    /// factorization of the denominator.
+    pub fn partial_fractions(
+        &self,
+        factors: &[(Polynomial<F>, Natural)],
+    ) -> Option<PartialFractionDecomposition<F>> {
+        let scope = tracing::span!(
+            tracing::Level::DEBUG,
+            "(5.11) Partial fraction decomposition",
+            f = format!("{self:?}"),
+            factors = format!("{factors:?}"),
+        );
+        let _enter = scope.enter();
+
+        let div_rem = |a: &Polynomial<F>, b: &Polynomial<F>| {
+            if a.deg() < b.deg() {
+                (Polynomial::zero(), a.clone())
+            } else {
+                a.div_rem(b).unwrap()
+            }
+        };
+
+        if factors.iter().any(|(g, _)| g.deg() == 0)
+            || factors
+                .iter()
+                .map(|(g, e)| g.pow(*e))
+                .fold(Polynomial::one(), |a, b| a * b)
+                .normal()
+                != self.denom
+        {
+            return None;
+        }
+
+        let (polynomial, r) = div_rem(&self.num, &self.denom);
+        tracing::debug!("q = {polynomial:?}, r = {r:?}");
+
+        let mut terms = vec![];
+        for (g, e) in factors {
+            let g = g.normal();
+            let m = g.pow(*e);
+            let rest = self.denom.clone() / m.clone();
+
+            let eea = extended_euclidean_algorithm(&rest, &m);
+            let d = eea.gcd();
+            if d.deg() > 0 {
+                return None;
+            }
+            let s = eea.s[eea.s.len() - 2].clone() * d.multiplicative_inverse()?;
+
+            // `a ≡ r/rest mod gᵉ`, which we expand `g`-adically
+            let mut a = (r.clone() * s) % m;
+            tracing::debug!("a = {a:?} mod ({g:?})^{e}");
+            for j in 0..*e {
+                let (quo, c) = div_rem(&a, &g);
+                if !c.is_zero() {
+                    terms.push((c, g.clone(), e - j));
+                }
+                a = quo;
+            }
+        }
+
+        Some(PartialFractionDecomposition { polynomial, terms })
+    }
+}
+
+/// The result of [`RationalFunction::partial_fractions`].
+pub struct PartialFractionDecomposition<F> {
+    /// The polynomial part `q`.
+    pub polynomial: Polynomial<F>,
+    /// The terms `(c, g, j)` each representing `c/g^j`.
+    pub terms: Vec<(Polynomial<F>, Polynomial<F>, Natural)>,
+}
+
+impl<F: Field> PartialFractionDecomposition<F> {
+    /// Adds up the terms of the decomposition into a single fraction.
+    pub fn recombine(&self) -> RationalFunction<F> {
+        self.terms
+            .iter()
+            .map(|(c, g, j)| Fraction::new(c.clone(), g.pow(*j)))
+            .fold(Fraction::from(self.polynomial.clone()), |a, b| a + b)
+    }
+}
+
+impl<F: Field> std::fmt::Debug for PartialFractionDecomposition<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let terms = std::iter::once(format!("{:?}", self.polynomial))
+            .filter(|_| !self.polynomial.is_zero())
+            .chain(self.terms.iter().map(|(c, g, j)| match j {
+                1 => format!("({c:?})/({g:?})"),
+                _ => format!("({c:?})/({g:?})^{j}"),
+            }))
+            .collect_vec();
+
+        if terms.is_empty() {
+            write!(f, "0")
+        } else {
+            write!(f, "{}", terms.iter().format(" + "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{latex::ToLatex, Integer, Rational};
+
+    fn poly(cs: &[i128]) -> Polynomial<Rational> {
+        Polynomial::new(cs.iter().copied().map(Rational::from).collect())
+    }
+
+    #[test]
+    fn normalization() {
+        // (2x² - 2)/(4x - 4) = (1/2 x + 1/2)/1
+        let f = Fraction::new(poly(&[-2, 0, 2]), poly(&[-4, 4]));
+
+        assert!(f.denom.is_one());
+        assert_eq!(f.num, poly(&[1, 1]).scale(&Rational { num: 1, denom: 2 }));
+
+        let r = Fraction::<Integer>::new(6, -4);
+        assert_eq!((r.num, r.denom), (-3, 2));
+    }
+
+    #[test]
+    fn field_operations() {
+        let f = Fraction::new(poly(&[1]), poly(&[0, 1]));
+        let g = Fraction::new(poly(&[1]), poly(&[1, 1]));
+
+        // 1/x - 1/(x + 1) = 1/(x² + x)
+        assert_eq!(
+            f.clone() - g.clone(),
+            Fraction::new(poly(&[1]), poly(&[0, 1, 1]))
+        );
+        assert_eq!(f.clone() / f.clone(), RationalFunction::one());
+        assert_eq!(
+            f.clone() * f.multiplicative_inverse().unwrap(),
+            RationalFunction::one()
+        );
+        assert_eq!(format!("{:?}", f + g), "(2x + 1)/(1x^2 + 1x)");
+    }
+
+    #[test]
+    fn evaluation() {
+        let f = Fraction::new(poly(&[1, 1]), poly(&[-1, 1]));
+
+        assert_eq!(f.evaluate_at(Rational::from(3)), Some(Rational::from(2)));
+        assert_eq!(f.evaluate_at(Rational::from(1)), None);
+    }
+
+    #[test]
+    fn partial_fractions() {
+        // (x³ + 1)/(x²(x - 1)) = 1 + (2)/(x - 1) + (-1)/x + (-1)/x²
+        let f = Fraction::new(poly(&[1, 0, 0, 1]), poly(&[0, 0, -1, 1]));
+        let pfd = f
+            .partial_fractions(&[(poly(&[0, 1]), 2), (poly(&[-1, 1]), 1)])
+            .unwrap();
+
+        assert_eq!(pfd.polynomial, poly(&[1]));
+        assert_eq!(pfd.terms.len(), 3);
+        assert!(pfd.terms.iter().all(|(c, g, _)| c.deg() < g.deg()));
+        assert_eq!(pfd.recombine(), f);
+
+        assert!(f.partial_fractions(&[(poly(&[0, 1]), 3)]).is_none());
+    }
+
+    #[test]
+    fn latex() {
+        let f = Fraction::new(poly(&[1, 0, 3]), poly(&[2, 1]));
+
+        assert_eq!(f.to_latex(), "\\frac{3x^{2} + 1}{x + 2}");
+    }
+}
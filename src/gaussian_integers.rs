@@ -134,9 +134,14 @@ impl<F: Identity<Multiplication> + Identity<Addition>> Identity<Multiplication>
 }
 impl<F: Ring> Ring for Gaussian<F> {
     fn multiplicative_inverse(&self) -> Option<Self> {
-        // TODO
-        // None
-        todo!()
+        // (a + bi)^-1 = (a - bi)/(aa + bb)
+        let norm = self.a.clone() * self.a.clone() + self.b.clone() * self.b.clone();
+        let inv = norm.multiplicative_inverse()?;
+
+        Some(Gaussian {
+            a: self.a.clone() * inv.clone(),
+            b: -self.b.clone() * inv,
+        })
     }
 }
 impl<F: Domain> Domain for Gaussian<F> {}
@@ -144,4 +149,15 @@ impl EuclideanDomain for Gaussian<Integer> {
     fn d(&self) -> Option<Natural> {
         Some(self.a.unsigned_abs().pow(2) + self.b.unsigned_abs().pow(2))
     }
+    /// The unit rotating `self` into the first quadrant, that is `a > 0` and
+    /// `b >= 0`.
+    fn lu(&self) -> Self {
+        match (self.a, self.b) {
+            (0, 0) => Gaussian::new(1, 0),
+            (a, b) if a > 0 && b >= 0 => Gaussian::new(1, 0),
+            (a, b) if a <= 0 && b > 0 => Gaussian::new(0, 1),
+            (a, b) if a < 0 && b <= 0 => Gaussian::new(-1, 0),
+            _ => Gaussian::new(0, -1),
+        }
+    }
 }
@@ -0,0 +1,201 @@
+//! # Hermite interpolation
+
+use std::fmt::Debug;
+
+use tracing::debug;
+
+use crate::{
+    ch05::{CraError, CrtBasis},
+    field::Field,
+    polynomials::Polynomial,
+    Group, Ring,
+};
+
+/// Hermite interpolation with the Chinese remainder algorithm.
+///
+/// Prescribing `f(uᵢ), f'(uᵢ), ..., f⁽ᵉⁱ⁻¹⁾(uᵢ)` at distinct points `uᵢ` is
+/// the same as prescribing `f mod (x - uᵢ)^eᵢ`, namely the truncated Taylor
+/// expansion `Σₖ f⁽ᵏ⁾(uᵢ)/k! (x - uᵢ)ᵏ`. These congruences are combined into
+/// the unique `f` of degree less than `Σ eᵢ` with a [`CrtBasis`].
+#[derive(Debug, Clone)]
+pub struct HermiteInterpolation<F: Field> {
+    /// The points `uᵢ` with the values `f(uᵢ), f'(uᵢ), ..., f⁽ᵉⁱ⁻¹⁾(uᵢ)`.
+    pub samples: Vec<(F, Vec<F>)>,
+    /// The moduli `(x - uᵢ)^eᵢ`.
+    pub moduli: Vec<Polynomial<F>>,
+    /// The Taylor expansions of `f` at `uᵢ` modulo `(x - uᵢ)^eᵢ`.
+    pub residues: Vec<Polynomial<F>>,
+    /// The interpolating polynomial.
+    pub f: Polynomial<F>,
+}
+
+#[derive(Debug)]
+pub enum HermiteInterpolationError {
+    PointsNotUnique,
+    /// Some point comes without any values.
+    NoValues,
+    /// Some `k!` vanishes in the characteristic of the field.
+    DerivativeOrderTooLarge,
+}
+
+impl<F: Field + Debug> std::fmt::Display for HermiteInterpolation<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use comfy_table::{
+            modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Attribute, Cell, ContentArrangement,
+            Table,
+        };
+
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_content_arrangement(ContentArrangement::Dynamic);
+
+        table.set_header(
+            ["i", "u", "f⁽ᵏ⁾(u)", "m", "f mod m"]
+                .map(|t| Cell::new(t).add_attribute(Attribute::Bold)),
+        );
+
+        for (i, ((u, vs), (m, r))) in self
+            .samples
+            .iter()
+            .zip(self.moduli.iter().zip(&self.residues))
+            .enumerate()
+        {
+            table.add_row([
+                format!("{i}"),
+                format!("{u:?}"),
+                format!("{vs:?}"),
+                format!("{m:?}"),
+                format!("{r:?}"),
+            ]);
+        }
+
+        writeln!(f, "{table}")?;
+        write!(f, "f = {:?}", self.f)
+    }
+}
+
+impl<F: Field + Debug> HermiteInterpolation<F> {
+    /// - Input: distinct `uᵢ` with `f(uᵢ), f'(uᵢ), ..., f⁽ᵉⁱ⁻¹⁾(uᵢ)`, where
+    ///   `eᵢ ≥ 1` and every `k < eᵢ` is invertible in `F`.
+    /// - Output: the unique `f` of degree `< Σ eᵢ` with these derivatives.
+    pub fn run(samples: &[(F, Vec<F>)]) -> Result<Self, HermiteInterpolationError> {
+        let scope = tracing::span!(
+            tracing::Level::DEBUG,
+            "Hermite interpolation",
+            samples = format!("{samples:?}"),
+        );
+        let _enter = scope.enter();
+
+        let mut moduli = vec![];
+        let mut residues = vec![];
+        for (u, vs) in samples {
+            if vs.is_empty() {
+                return Err(HermiteInterpolationError::NoValues);
+            }
+            let x_minus_u = Polynomial::new(vec![-u.clone(), F::one()]);
+
+            // Σₖ vₖ/k! (x - u)ᵏ
+            let mut residue = Polynomial::zero();
+            let mut power = Polynomial::one();
+            let mut k = F::zero();
+            let mut factorial = F::one();
+            for (i, v) in vs.iter().enumerate() {
+                if i > 0 {
+                    k = k + F::one();
+                    factorial = factorial * k.clone();
+                    power = power * x_minus_u.clone();
+                }
+                let c = factorial
+                    .multiplicative_inverse()
+                    .ok_or(HermiteInterpolationError::DerivativeOrderTooLarge)?
+                    * v.clone();
+                residue = residue + power.scale(&c);
+            }
+            let modulus = power * x_minus_u;
+            debug!("f ≡ {residue:?} mod {modulus:?}");
+
+            moduli.push(modulus);
+            residues.push(residue);
+        }
+
+        let f = CrtBasis::new(&moduli)
+            .and_then(|basis| basis.reconstruct(&residues))
+            .map_err(|e| match e {
+                CraError::NotCoprime { .. } => HermiteInterpolationError::PointsNotUnique,
+                e => unreachable!("the moduli are nonzero and match the residues: {e:?}"),
+            })?;
+        debug!("f = {f:?}");
+
+        Ok(HermiteInterpolation {
+            samples: samples.to_vec(),
+            moduli,
+            residues,
+            f,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Finite, Rational};
+
+    use super::*;
+
+    fn rationals(xs: &[i128]) -> Vec<Rational> {
+        xs.iter().copied().map(Rational::from).collect()
+    }
+
+    #[test]
+    fn values_and_derivatives() -> Result<(), HermiteInterpolationError> {
+        // f(0) = 1, f'(0) = 0, f''(0) = 2 and f(1) = 3, f'(1) = 5
+        let samples = [
+            (Rational::zero(), rationals(&[1, 0, 2])),
+            (Rational::one(), rationals(&[3, 5])),
+        ];
+        let hermite = HermiteInterpolation::run(&samples)?;
+        let f = &hermite.f;
+
+        assert!(f.deg() < 5);
+        for (u, vs) in &samples {
+            let mut g = f.clone();
+            for v in vs {
+                assert_eq!(g.evaluate_at(*u), *v);
+                g = g.diff();
+            }
+        }
+        // x³ + x² + 1
+        assert_eq!(*f, Polynomial::new(rationals(&[1, 0, 1, 1])));
+        assert!(hermite.to_string().contains(&format!("f = {f:?}")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn failures() {
+        let samples = [
+            (Finite::<5>::from(1i128), vec![Finite::from(2i128)]),
+            (Finite::from(6i128), vec![Finite::from(3i128)]),
+        ];
+        assert!(matches!(
+            HermiteInterpolation::run(&samples),
+            Err(HermiteInterpolationError::PointsNotUnique)
+        ));
+
+        let samples = [(Finite::<3>::zero(), vec![Finite::one(); 4])];
+        assert!(matches!(
+            HermiteInterpolation::run(&samples),
+            Err(HermiteInterpolationError::DerivativeOrderTooLarge)
+        ));
+
+        let samples = [
+            (Rational::zero(), rationals(&[1])),
+            (Rational::one(), vec![]),
+        ];
+        assert!(matches!(
+            HermiteInterpolation::run(&samples),
+            Err(HermiteInterpolationError::NoValues)
+        ));
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     euclidean_domain::EuclideanDomain,
     group::AbelianGroup,
     identity::{Addition, Identity, Multiplication},
-    Group, Natural, Ring,
+    naturals, Group, Natural, Ring,
 };
 
 pub type Integer = i128;
@@ -36,4 +36,52 @@ impl EuclideanDomain for i128 {
     fn d(&self) -> Option<Natural> {
         Some(self.unsigned_abs())
     }
+    fn lu(&self) -> Self {
+        if *self < 0 {
+            -1
+        } else {
+            1
+        }
+    }
+    fn rem_canonical(&self, m: &Self) -> Self {
+        self.rem_euclid(*m)
+    }
+}
+
+/// `ab mod m` in `[0, |m|)` for `m ≠ 0`, without overflowing even when the
+/// product `ab` does not fit in an [`Integer`], by [`naturals::mul_mod`].
+pub fn mul_mod(a: Integer, b: Integer, m: Integer) -> Integer {
+    assert!(m != 0, "the modulus must be nonzero");
+
+    let (a, b) = (a.rem_euclid(m) as Natural, b.rem_euclid(m) as Natural);
+    naturals::mul_mod(a, b, m.unsigned_abs()) as Integer
+}
+
+/// `aᵉ mod m` in `[0, |m|)` for `m ≠ 0`, by [`naturals::pow_mod`].
+pub fn pow_mod(a: Integer, e: Natural, m: Integer) -> Integer {
+    naturals::pow_mod(a.rem_euclid(m) as Natural, e, m.unsigned_abs()) as Integer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modular_arithmetic() {
+        assert_eq!(mul_mod(-7, 5, 12), 1);
+        assert_eq!(mul_mod(7, 5, -12), 11);
+
+        // (2¹²⁶ - 1)² ≡ 2¹²⁵ mod 2¹²⁷ - 1 overflows the direct product
+        let m = Integer::MAX;
+        let a = (1 << 126) - 1;
+        assert_eq!(mul_mod(a, a, m), 1 << 125);
+        assert_eq!(pow_mod(a, 2, m), 1 << 125);
+        assert_eq!(mul_mod(a, 2, m), m - 1);
+
+        // Fermat's little theorem for the Mersenne prime 2¹²⁷ - 1
+        assert_eq!(pow_mod(3, (m - 1) as Natural, m), 1);
+        assert_eq!(pow_mod(2, 127, m), 1);
+        assert_eq!(pow_mod(5, 0, 1), 0);
+        assert_eq!(pow_mod(-2, 3, 13), 5);
+    }
 }
@@ -0,0 +1,242 @@
+//! # Polynomial interpolation
+//!
+//! The interpolating polynomial of degree `< n` through `n` samples
+//! `(uᵢ, vᵢ)` with distinct `uᵢ` is unique, so every [`Interpolator`]
+//! returns the same [`Polynomial`] and they differ only in their cost.
+
+use std::fmt::Debug;
+
+use tracing::debug;
+
+use crate::{field::Field, polynomials::Polynomial, Group, Natural, Ring};
+
+/// A method computing the interpolating polynomial of degree `< n` through
+/// the samples `(u₀, v₀), ..., (uₙ₋₁, vₙ₋₁)`.
+pub trait Interpolator<F: Field> {
+    type Error: Debug;
+
+    fn interpolate(samples: &[(F, F)]) -> Result<Polynomial<F>, Self::Error>;
+}
+
+#[derive(Debug)]
+pub enum InterpolationError {
+    SamplesNotUnique,
+}
+
+/// `x - u`
+fn linear<F: Field>(u: &F) -> Polynomial<F> {
+    Polynomial::new(vec![-u.clone(), F::one()])
+}
+
+/// The weights `wᵢ = 1 / ∏_{j≠i} (uᵢ - uⱼ)`, using `n(n - 1)` subtractions
+/// and multiplications and `n` inversions.
+fn weights<F: Field>(samples: &[(F, F)]) -> Result<Vec<F>, InterpolationError> {
+    samples
+        .iter()
+        .enumerate()
+        .map(|(i, (ui, _))| {
+            let d = samples
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold(F::one(), |d, (_, (uj, _))| d * (ui.clone() - uj.clone()));
+            if d.is_zero() {
+                return Err(InterpolationError::SamplesNotUnique);
+            }
+            Ok(F::one() / d)
+        })
+        .collect()
+}
+
+/// Lagrange interpolation.
+///
+/// `f = Σ vᵢ lᵢ/lᵢ(uᵢ)` for the Lagrange polynomials
+/// `lᵢ = ∏_{j≠i} (x - uⱼ)`, each of which is computed from scratch, for
+/// `O(n³)` operations in `F`.
+pub struct LagrangeInterpolation;
+
+impl<F: Field + Debug> Interpolator<F> for LagrangeInterpolation {
+    type Error = InterpolationError;
+
+    fn interpolate(samples: &[(F, F)]) -> Result<Polynomial<F>, Self::Error> {
+        let scope = tracing::span!(
+            tracing::Level::DEBUG,
+            "Lagrange interpolation",
+            samples = format!("{samples:?}"),
+        );
+        let _enter = scope.enter();
+
+        let mut f = Polynomial::zero();
+        for (i, (ui, vi)) in samples.iter().enumerate() {
+            let li = samples
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold(Polynomial::one(), |l, (_, (uj, _))| l * linear(uj));
+            let d = li.evaluate_at(ui.clone());
+            if d.is_zero() {
+                return Err(InterpolationError::SamplesNotUnique);
+            }
+            debug!("l{i} = {li:?}, l{i}(u{i}) = {d:?}");
+
+            f = f + li.scale(&(vi.clone() / d));
+        }
+
+        Ok(f)
+    }
+}
+
+/// Barycentric interpolation.
+///
+/// With the weights `wᵢ = 1 / ∏_{j≠i} (uᵢ - uⱼ)` and `l = ∏ (x - uⱼ)` the
+/// interpolating polynomial is `f = Σ wᵢ vᵢ l/(x - uᵢ)`, for `O(n²)`
+/// operations in `F`. Once the weights are known, `f` can also be evaluated
+/// at a point with `O(n)` operations without computing its coefficients.
+#[derive(Debug, Clone)]
+pub struct BarycentricInterpolation<F> {
+    pub samples: Vec<(F, F)>,
+    pub weights: Vec<F>,
+}
+
+impl<F: Field + Debug> BarycentricInterpolation<F> {
+    pub fn new(samples: &[(F, F)]) -> Result<Self, InterpolationError> {
+        let weights = weights(samples)?;
+        debug!("w = {weights:?}");
+
+        Ok(BarycentricInterpolation {
+            samples: samples.to_vec(),
+            weights,
+        })
+    }
+
+    /// The second barycentric formula
+    /// `f(x) = (Σ wᵢ vᵢ/(x - uᵢ)) / (Σ wᵢ/(x - uᵢ))` for `x ≠ uᵢ`.
+    pub fn evaluate(&self, x: &F) -> F {
+        if let Some((_, v)) = self.samples.iter().find(|(u, _)| u == x) {
+            return v.clone();
+        }
+
+        let (num, denom) = self.samples.iter().zip(&self.weights).fold(
+            (F::zero(), F::zero()),
+            |(num, denom), ((u, v), w)| {
+                let t = w.clone() / (x.clone() - u.clone());
+                (num + t.clone() * v.clone(), denom + t)
+            },
+        );
+        num / denom
+    }
+
+    /// The coefficients of `f = Σ wᵢ vᵢ l/(x - uᵢ)`, dividing `l` by each
+    /// `x - uᵢ` with Horner's rule.
+    pub fn polynomial(&self) -> Polynomial<F> {
+        let l = self
+            .samples
+            .iter()
+            .fold(Polynomial::one(), |l, (u, _)| l * linear(u));
+        let n = self.samples.len();
+
+        let mut f = vec![F::zero(); n];
+        for ((u, v), w) in self.samples.iter().zip(&self.weights) {
+            let c = w.clone() * v.clone();
+            let mut q = F::zero();
+            for k in (0..n).rev() {
+                q = l.coef_at(k as Natural + 1) + u.clone() * q;
+                f[k] = f[k].clone() + c.clone() * q.clone();
+            }
+        }
+
+        Polynomial::new(f)
+    }
+}
+
+impl<F: Field + Debug> Interpolator<F> for BarycentricInterpolation<F> {
+    type Error = InterpolationError;
+
+    fn interpolate(samples: &[(F, F)]) -> Result<Polynomial<F>, Self::Error> {
+        let scope = tracing::span!(
+            tracing::Level::DEBUG,
+            "Barycentric interpolation",
+            samples = format!("{samples:?}"),
+        );
+        let _enter = scope.enter();
+
+        Ok(BarycentricInterpolation::new(samples)?.polynomial())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        count_ops::{self, CountOps},
+        newton_interpolation::NewtonInterpolation,
+        Finite, Rational,
+    };
+
+    use super::*;
+
+    type F = CountOps<Finite<101>>;
+
+    fn samples(n: i128) -> Vec<(F, F)> {
+        (0..n)
+            .map(|i| {
+                (
+                    Finite::from(3 * i + 1).into(),
+                    Finite::from(i * i * i + 7).into(),
+                )
+            })
+            .collect()
+    }
+
+    fn check<I: Interpolator<F>>(samples: &[(F, F)]) -> (Polynomial<F>, u32) {
+        count_ops::reset();
+        let f = I::interpolate(samples).unwrap();
+        let counts = count_ops::get_counts();
+
+        for (u, v) in samples {
+            assert_eq!(f.evaluate_at(*u), *v, "f({u:?}) != {v:?}");
+        }
+        (f, counts.additions + counts.multiplications)
+    }
+
+    #[test]
+    fn same_polynomial() {
+        let samples = samples(12);
+
+        let (lagrange, lagrange_ops) = check::<LagrangeInterpolation>(&samples);
+        let (barycentric, barycentric_ops) = check::<BarycentricInterpolation<F>>(&samples);
+        let (newton, newton_ops) = check::<NewtonInterpolation<F>>(&samples);
+
+        assert_eq!(lagrange, barycentric);
+        assert_eq!(lagrange, newton);
+        assert!(lagrange.deg() < 12);
+
+        assert!(barycentric_ops < lagrange_ops);
+        assert!(newton_ops < lagrange_ops);
+    }
+
+    #[test]
+    fn barycentric_evaluation() {
+        let samples = [(-5, -2), (-1, 6), (0, -1), (2, 3)]
+            .map(|(u, v)| (Rational::from(u), Rational::from(v)));
+        let barycentric = BarycentricInterpolation::new(&samples).unwrap();
+        let f = barycentric.polynomial();
+
+        for x in [-5, -3, 0, 1, 7].map(Rational::from) {
+            assert_eq!(barycentric.evaluate(&x), f.evaluate_at(x));
+        }
+    }
+
+    #[test]
+    fn duplicate_samples() {
+        let samples = [(1, 2), (3, 4), (1, 5)].map(|(u, v)| (Rational::from(u), Rational::from(v)));
+
+        assert!(matches!(
+            LagrangeInterpolation::interpolate(&samples),
+            Err(InterpolationError::SamplesNotUnique)
+        ));
+        assert!(matches!(
+            BarycentricInterpolation::interpolate(&samples),
+            Err(InterpolationError::SamplesNotUnique)
+        ));
+    }
+}
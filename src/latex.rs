@@ -1,8 +1,10 @@
 use crate::{
+    euclidean_domain::EuclideanDomain,
+    fractions::Fraction,
     identity::{Addition, Identity, Multiplication},
     mono::{Monomial, MonomialOrder},
     multivariate_polynomials::MultivariatePolynomial,
-    Finite, Natural, Rational, Ring,
+    Finite, Integer, Natural, Polynomial, Rational, Ring,
 };
 use itertools::Itertools;
 
@@ -79,6 +81,63 @@ where
     }
 }
 
+impl<F> ToLatex for Polynomial<F>
+where
+    F: ToLatex + Ring,
+{
+    fn to_latex(&self) -> String {
+        let s = format!(
+            "{}",
+            self.iter()
+                .filter(|(c, _)| !c.is_zero())
+                .map(|(c, i)| {
+                    let c = if c.is_one() && i > 0 {
+                        "".to_string()
+                    } else {
+                        c.to_latex()
+                    };
+                    match i {
+                        0 => c,
+                        1 => format!("{c}x"),
+                        _ => format!("{c}x^{{{i}}}"),
+                    }
+                })
+                .collect_vec()
+                .into_iter()
+                .rev()
+                .format(" + ")
+        );
+        if s.is_empty() {
+            "0".to_string()
+        } else {
+            s
+        }
+    }
+}
+
+impl<D> ToLatex for Fraction<D>
+where
+    D: ToLatex + EuclideanDomain,
+{
+    fn to_latex(&self) -> String {
+        if self.denom.is_one() {
+            self.num.to_latex()
+        } else {
+            format!(
+                "\\frac{{{}}}{{{}}}",
+                self.num.to_latex(),
+                self.denom.to_latex()
+            )
+        }
+    }
+}
+
+impl ToLatex for Integer {
+    fn to_latex(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
 impl ToLatex for Rational {
     fn to_latex(&self) -> String {
         if self.denom == 1 {
@@ -1,8 +1,15 @@
 pub mod ch03;
 pub mod ch05;
+pub mod ch06;
 pub mod ch08;
 pub mod ch09;
 pub mod ch10;
+pub mod ch12;
+pub mod ch14;
+pub mod ch15;
+pub mod ch16;
+pub mod ch18;
+pub mod ch19;
 pub mod ch21;
 pub mod count_ops;
 pub mod dft;
@@ -10,21 +17,30 @@ pub mod domain;
 pub mod euclidean_domain;
 pub mod field;
 pub mod finite;
+pub mod fractions;
 pub mod gaussian_integers;
 pub mod group;
+pub mod hermite_interpolation;
 pub mod identity;
 pub mod integers;
+pub mod interpolation;
 pub mod latex;
+pub mod matrix;
 pub mod mono;
 pub mod multivariate_polynomials;
 pub mod naturals;
 pub mod newton_interpolation;
+pub mod number_theory;
 pub mod polynomials;
 pub mod rationals;
 pub mod reals;
 pub mod ring;
+pub mod roots;
+pub mod sparse_polynomials;
+pub mod sturm;
 
 pub use finite::Finite;
+pub use fractions::Fraction;
 pub use group::Group;
 pub use integers::Integer;
 pub use mono::Monomial;
@@ -0,0 +1,903 @@
+//! # Matrices and linear algebra
+
+use itertools::Itertools;
+use tracing::debug;
+
+use crate::{
+    ch03::extended_euclidean_algorithm, euclidean_domain::EuclideanDomain, field::Field, Group,
+    Integer, Polynomial, Ring,
+};
+
+/// A dense `rows × cols` matrix over a ring `R`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Matrix<R> {
+    rows: usize,
+    cols: usize,
+    /// The entries row by row, that is:
+    /// ```ignore
+    /// [[a, b], [c, d]] == [a, b, c, d]
+    /// ```
+    entries: Vec<R>,
+}
+
+impl<R: std::fmt::Debug> std::fmt::Debug for Matrix<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}]",
+            self.entries
+                .chunks(self.cols.max(1))
+                .map(|row| format!("[{}]", row.iter().map(|c| format!("{c:?}")).join(", ")))
+                .join(", ")
+        )
+    }
+}
+
+impl<R: std::fmt::Debug> std::fmt::Display for Matrix<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use comfy_table::{
+            modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, ContentArrangement, Table,
+        };
+
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_content_arrangement(ContentArrangement::Dynamic);
+
+        for row in self.entries.chunks(self.cols.max(1)) {
+            table.add_row(row.iter().map(|c| format!("{c:?}")));
+        }
+
+        write!(f, "{table}")
+    }
+}
+
+impl<R> std::ops::Index<(usize, usize)> for Matrix<R> {
+    type Output = R;
+
+    fn index(&self, (i, j): (usize, usize)) -> &Self::Output {
+        assert!(
+            i < self.rows && j < self.cols,
+            "({i}, {j}) is out of bounds"
+        );
+        &self.entries[i * self.cols + j]
+    }
+}
+impl<R> std::ops::IndexMut<(usize, usize)> for Matrix<R> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            i < self.rows && j < self.cols,
+            "({i}, {j}) is out of bounds"
+        );
+        &mut self.entries[i * self.cols + j]
+    }
+}
+
+impl<R: Clone> Matrix<R> {
+    /// The matrix with the given rows, which must all have the same length.
+    pub fn new(rows: Vec<Vec<R>>) -> Self {
+        let cols = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == cols),
+            "the rows have different lengths"
+        );
+
+        Matrix {
+            rows: rows.len(),
+            cols,
+            entries: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// The matrix with the entries `f(i, j)`.
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> R) -> Self {
+        Matrix {
+            rows,
+            cols,
+            entries: (0..rows)
+                .cartesian_product(0..cols)
+                .map(|(i, j)| f(i, j))
+                .collect(),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn row(&self, i: usize) -> &[R] {
+        &self.entries[i * self.cols..(i + 1) * self.cols]
+    }
+    pub fn column(&self, j: usize) -> Vec<R> {
+        (0..self.rows).map(|i| self[(i, j)].clone()).collect()
+    }
+    pub fn to_rows(&self) -> Vec<Vec<R>> {
+        (0..self.rows).map(|i| self.row(i).to_vec()).collect()
+    }
+
+    pub fn transpose(&self) -> Self {
+        Matrix::from_fn(self.cols, self.rows, |i, j| self[(j, i)].clone())
+    }
+
+    pub fn swap_rows(&mut self, i: usize, j: usize) {
+        for k in 0..self.cols {
+            self.entries.swap(i * self.cols + k, j * self.cols + k);
+        }
+    }
+
+    pub fn swap_columns(&mut self, i: usize, j: usize) {
+        for k in 0..self.rows {
+            self.entries.swap(k * self.cols + i, k * self.cols + j);
+        }
+    }
+
+    /// The matrix of the rows `rows` and columns `cols` of `self`.
+    pub fn submatrix(
+        &self,
+        rows: impl Clone + Iterator<Item = usize>,
+        cols: impl Clone + Iterator<Item = usize>,
+    ) -> Self {
+        let entries = rows
+            .clone()
+            .cartesian_product(cols.clone())
+            .map(|(i, j)| self[(i, j)].clone())
+            .collect();
+        Matrix {
+            rows: rows.count(),
+            cols: cols.count(),
+            entries,
+        }
+    }
+}
+
+impl<R: Ring> Matrix<R> {
+    pub fn zero(rows: usize, cols: usize) -> Self {
+        Matrix::from_fn(rows, cols, |_, _| R::zero())
+    }
+
+    pub fn identity(n: usize) -> Self {
+        Matrix::from_fn(n, n, |i, j| if i == j { R::one() } else { R::zero() })
+    }
+
+    /// The Vandermonde matrix `(uᵢʲ)` with `n` columns, which maps the
+    /// coefficients of a polynomial of degree `< n` to its values at the
+    /// points `uᵢ`.
+    pub fn vandermonde(us: &[R], n: usize) -> Self {
+        Matrix::from_fn(us.len(), n, |i, j| us[i].pow(j as _))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.entries.iter().all(|c| c.is_zero())
+    }
+
+    pub fn scale(&self, s: &R) -> Self {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            entries: self.entries.iter().map(|c| c.clone() * s.clone()).collect(),
+        }
+    }
+
+    /// The product `Av` with the column vector `v`.
+    pub fn apply(&self, v: &[R]) -> Vec<R> {
+        assert_eq!(self.cols, v.len(), "the dimensions do not match");
+
+        (0..self.rows)
+            .map(|i| {
+                self.row(i)
+                    .iter()
+                    .zip(v)
+                    .fold(R::zero(), |sum, (a, b)| sum + a.clone() * b.clone())
+            })
+            .collect()
+    }
+}
+
+/// The result of Gaussian elimination: an echelon form, its pivot columns and
+/// whether an odd number of rows were swapped.
+struct Elimination<R> {
+    matrix: Matrix<R>,
+    pivots: Vec<usize>,
+    odd: bool,
+}
+
+impl<F: Field> Matrix<F> {
+    /// Gaussian elimination with row swaps. Above the pivots the entries are
+    /// eliminated as well if `reduced`, and the pivots are normalized to `1`.
+    fn eliminate(&self, reduced: bool) -> Elimination<F> {
+        let mut a = self.clone();
+        let mut pivots = vec![];
+        let mut odd = false;
+
+        for col in 0..a.cols {
+            let r = pivots.len();
+            let Some(i) = (r..a.rows).find(|&i| !a[(i, col)].is_zero()) else {
+                continue;
+            };
+            if i != r {
+                a.swap_rows(i, r);
+                odd = !odd;
+            }
+
+            if reduced {
+                let inv = a[(r, col)]
+                    .multiplicative_inverse()
+                    .expect("nonzero elements of a field are units");
+                for j in col..a.cols {
+                    a[(r, j)] = a[(r, j)].clone() * inv.clone();
+                }
+            }
+            let pivot = a[(r, col)].clone();
+            let rows = if reduced { 0..a.rows } else { r + 1..a.rows };
+            for i in rows.filter(|&i| i != r) {
+                if a[(i, col)].is_zero() {
+                    continue;
+                }
+                let factor = a[(i, col)].clone() / pivot.clone();
+                for j in col..a.cols {
+                    a[(i, j)] = a[(i, j)].clone() - factor.clone() * a[(r, j)].clone();
+                }
+            }
+            pivots.push(col);
+        }
+
+        Elimination {
+            matrix: a,
+            pivots,
+            odd,
+        }
+    }
+
+    /// A row echelon form by Gaussian elimination, with its pivot columns.
+    pub fn row_echelon_form(&self) -> (Self, Vec<usize>) {
+        let Elimination { matrix, pivots, .. } = self.eliminate(false);
+        (matrix, pivots)
+    }
+
+    /// The reduced row echelon form by Gauss–Jordan elimination, with its
+    /// pivot columns.
+    pub fn reduced_row_echelon_form(&self) -> (Self, Vec<usize>) {
+        let Elimination { matrix, pivots, .. } = self.eliminate(true);
+        (matrix, pivots)
+    }
+
+    pub fn rank(&self) -> usize {
+        self.eliminate(false).pivots.len()
+    }
+
+    /// A basis of the kernel `{v : Av = 0}`, with one vector for each
+    /// non-pivot column of the reduced row echelon form.
+    pub fn kernel(&self) -> Vec<Vec<F>> {
+        let (a, pivots) = self.reduced_row_echelon_form();
+
+        (0..a.cols)
+            .filter(|c| !pivots.contains(c))
+            .map(|free| {
+                let mut v = vec![F::zero(); a.cols];
+                v[free] = F::one();
+                for (row, &pivot) in pivots.iter().enumerate() {
+                    v[pivot] = -a[(row, free)].clone();
+                }
+                v
+            })
+            .collect()
+    }
+
+    /// A solution `v` of `Av = b`, if there is one.
+    pub fn solve(&self, b: &[F]) -> Option<Vec<F>> {
+        assert_eq!(self.rows, b.len(), "the dimensions do not match");
+
+        let augmented = Matrix::from_fn(self.rows, self.cols + 1, |i, j| {
+            if j < self.cols {
+                self[(i, j)].clone()
+            } else {
+                b[i].clone()
+            }
+        });
+        let (a, pivots) = augmented.reduced_row_echelon_form();
+        if pivots.last() == Some(&self.cols) {
+            return None;
+        }
+
+        let mut v = vec![F::zero(); self.cols];
+        for (row, &pivot) in pivots.iter().enumerate() {
+            v[pivot] = a[(row, self.cols)].clone();
+        }
+        Some(v)
+    }
+
+    /// The inverse by Gauss–Jordan elimination of `(A | I)`, if `A` is
+    /// square and nonsingular.
+    pub fn inverse(&self) -> Option<Self> {
+        assert!(self.is_square(), "only square matrices are invertible");
+        let n = self.rows;
+
+        let augmented = Matrix::from_fn(n, 2 * n, |i, j| {
+            if j < n {
+                self[(i, j)].clone()
+            } else if j - n == i {
+                F::one()
+            } else {
+                F::zero()
+            }
+        });
+        let (a, pivots) = augmented.reduced_row_echelon_form();
+        if pivots.len() < n || pivots[n - 1] >= n {
+            return None;
+        }
+        Some(a.submatrix(0..n, n..2 * n))
+    }
+
+    /// The determinant as the signed product of the pivots of a row echelon
+    /// form.
+    pub fn determinant(&self) -> F {
+        assert!(self.is_square(), "the determinant needs a square matrix");
+
+        let Elimination {
+            matrix,
+            pivots,
+            odd,
+        } = self.eliminate(false);
+        if pivots.len() < self.rows {
+            return F::zero();
+        }
+        let det = (0..self.rows).fold(F::one(), |det, i| det * matrix[(i, i)].clone());
+        if odd {
+            -det
+        } else {
+            det
+        }
+    }
+
+    /// The characteristic polynomial `det(xI - A)`, by fraction-free
+    /// elimination over `F[x]`.
+    pub fn characteristic_polynomial(&self) -> Polynomial<F> {
+        assert!(self.is_square(), "the matrix must be square");
+
+        Matrix::from_fn(self.rows, self.cols, |i, j| {
+            let x: Polynomial<F> = if i == j {
+                Polynomial::x()
+            } else {
+                Polynomial::zero()
+            };
+            x - Polynomial::new(vec![self[(i, j)].clone()])
+        })
+        .fraction_free_determinant()
+    }
+}
+
+impl<R> Matrix<R>
+where
+    R: Ring + std::ops::Div<Output = R>,
+{
+    fn bareiss(&self) -> Elimination<R> {
+        let scope = tracing::span!(
+            tracing::Level::DEBUG,
+            "Bareiss elimination",
+            a = format!("{self:?}"),
+        );
+        let _enter = scope.enter();
+
+        let mut a = self.clone();
+        let mut pivots = vec![];
+        let mut odd = false;
+        let mut prev = R::one();
+
+        for col in 0..a.cols {
+            let r = pivots.len();
+            let Some(i) = (r..a.rows).find(|&i| !a[(i, col)].is_zero()) else {
+                continue;
+            };
+            if i != r {
+                a.swap_rows(i, r);
+                odd = !odd;
+            }
+
+            for i in r + 1..a.rows {
+                for j in col + 1..a.cols {
+                    a[(i, j)] = (a[(r, col)].clone() * a[(i, j)].clone()
+                        - a[(i, col)].clone() * a[(r, j)].clone())
+                        / prev.clone();
+                }
+                a[(i, col)] = R::zero();
+            }
+            prev = a[(r, col)].clone();
+            pivots.push(col);
+            debug!("pivot {prev:?} in column {col}: {a:?}");
+        }
+
+        Elimination {
+            matrix: a,
+            pivots,
+            odd,
+        }
+    }
+
+    /// A row echelon form by fraction-free (Bareiss) elimination, with its
+    /// pivot columns.
+    ///
+    /// Each division `(pᵣ aᵢⱼ - aᵢ꜀ aᵣⱼ) / pᵣ₋₁` by the previous pivot is
+    /// exact and all entries are minors of `A`, so `R` may be an integral
+    /// domain such as the integers or a polynomial ring over a field, where
+    /// `/` is only required to be correct for exact quotients.
+    pub fn fraction_free_echelon_form(&self) -> (Self, Vec<usize>) {
+        let Elimination { matrix, pivots, .. } = self.bareiss();
+        (matrix, pivots)
+    }
+
+    /// The determinant as the last pivot of [`Self::fraction_free_echelon_form`],
+    /// up to the sign of the row swaps.
+    pub fn fraction_free_determinant(&self) -> R {
+        assert!(self.is_square(), "the determinant needs a square matrix");
+        if self.rows == 0 {
+            return R::one();
+        }
+
+        let Elimination {
+            matrix,
+            pivots,
+            odd,
+        } = self.bareiss();
+        if pivots.len() < self.rows {
+            return R::zero();
+        }
+        let det = matrix[(self.rows - 1, self.cols - 1)].clone();
+        if odd {
+            -det
+        } else {
+            det
+        }
+    }
+}
+
+/// `(g, s, t)` with `g = gcd(a, b) = sa + tb ≥ 0`, from the
+/// [`extended_euclidean_algorithm`].
+fn bezout(a: Integer, b: Integer) -> (Integer, Integer, Integer) {
+    let eea = extended_euclidean_algorithm(&a, &b);
+    let l = eea.r.len() - 2;
+    let u = eea.r[l].lu();
+    (eea.r[l] * u, eea.s[l] * u, eea.t[l] * u)
+}
+
+/// The unimodular `[[s, t], [-b/g, a/g]]` mapping `(a, b)` to `(g, 0)`.
+fn unimodular(a: Integer, b: Integer) -> [[Integer; 2]; 2] {
+    let (g, s, t) = bezout(a, b);
+    [[s, t], [-b / g, a / g]]
+}
+
+impl Matrix<Integer> {
+    /// Replaces the rows `i` and `j` by `p₀₀ rowᵢ + p₀₁ rowⱼ` and
+    /// `p₁₀ rowᵢ + p₁₁ rowⱼ`.
+    fn combine_rows(&mut self, i: usize, j: usize, [[p00, p01], [p10, p11]]: [[Integer; 2]; 2]) {
+        for k in 0..self.cols {
+            let (x, y) = (self[(i, k)], self[(j, k)]);
+            self[(i, k)] = p00 * x + p01 * y;
+            self[(j, k)] = p10 * x + p11 * y;
+        }
+    }
+
+    fn negate_row(&mut self, i: usize) {
+        for k in 0..self.cols {
+            self[(i, k)] = -self[(i, k)];
+        }
+    }
+
+    /// Replaces the columns `i` and `j` by `p₀₀ colᵢ + p₀₁ colⱼ` and
+    /// `p₁₀ colᵢ + p₁₁ colⱼ`.
+    fn combine_columns(&mut self, i: usize, j: usize, [[p00, p01], [p10, p11]]: [[Integer; 2]; 2]) {
+        for k in 0..self.rows {
+            let (x, y) = (self[(k, i)], self[(k, j)]);
+            self[(k, i)] = p00 * x + p01 * y;
+            self[(k, j)] = p10 * x + p11 * y;
+        }
+    }
+
+    /// The Hermite normal form `H = UA` with a unimodular `U`.
+    ///
+    /// `H` is in row echelon form, its pivots are positive and the entries
+    /// above each pivot `p` lie in `[0, p)`. The entries below a pivot are
+    /// eliminated by the row operations `[[s, t], [-b/g, a/g]]` of
+    /// determinant `1` from `g = gcd(a, b) = sa + tb`.
+    pub fn hermite_normal_form(&self) -> (Self, Self) {
+        let scope = tracing::span!(
+            tracing::Level::DEBUG,
+            "Hermite normal form",
+            a = format!("{self:?}"),
+        );
+        let _enter = scope.enter();
+
+        let mut h = self.clone();
+        let mut u = Matrix::identity(self.rows);
+        let mut r = 0;
+
+        for col in 0..h.cols {
+            if r == h.rows {
+                break;
+            }
+            for i in r + 1..h.rows {
+                if h[(i, col)] != 0 {
+                    let p = unimodular(h[(r, col)], h[(i, col)]);
+                    h.combine_rows(r, i, p);
+                    u.combine_rows(r, i, p);
+                }
+            }
+            if h[(r, col)] == 0 {
+                continue;
+            }
+            if h[(r, col)] < 0 {
+                h.negate_row(r);
+                u.negate_row(r);
+            }
+            for i in 0..r {
+                let q = h[(i, col)].div_euclid(h[(r, col)]);
+                h.combine_rows(i, r, [[1, -q], [0, 1]]);
+                u.combine_rows(i, r, [[1, -q], [0, 1]]);
+            }
+            debug!("pivot {} in column {col}: H = {h:?}", h[(r, col)]);
+            r += 1;
+        }
+
+        (h, u)
+    }
+
+    /// The Smith normal form `S = UAV` with unimodular `U` and `V`.
+    ///
+    /// `S` is diagonal with nonnegative `d₁ | d₂ | ··· | dᵣ` followed by
+    /// zeros. Each pivot is chosen with minimal absolute value, its row and
+    /// column are cleared with gcd steps as in [`Self::hermite_normal_form`],
+    /// and a row with an entry that the pivot does not divide is added to the
+    /// pivot row before clearing again, which decreases the pivot.
+    pub fn smith_normal_form(&self) -> (Self, Self, Self) {
+        let scope = tracing::span!(
+            tracing::Level::DEBUG,
+            "Smith normal form",
+            a = format!("{self:?}"),
+        );
+        let _enter = scope.enter();
+
+        let (m, n) = (self.rows, self.cols);
+        let mut s = self.clone();
+        let mut u = Matrix::identity(m);
+        let mut v = Matrix::identity(n);
+
+        for t in 0..m.min(n) {
+            let Some((i, j)) = (t..m)
+                .cartesian_product(t..n)
+                .filter(|&(i, j)| s[(i, j)] != 0)
+                .min_by_key(|&(i, j)| s[(i, j)].unsigned_abs())
+            else {
+                break;
+            };
+            s.swap_rows(t, i);
+            u.swap_rows(t, i);
+            s.swap_columns(t, j);
+            v.swap_columns(t, j);
+
+            loop {
+                for i in t + 1..m {
+                    if s[(i, t)] != 0 {
+                        let p = unimodular(s[(t, t)], s[(i, t)]);
+                        s.combine_rows(t, i, p);
+                        u.combine_rows(t, i, p);
+                    }
+                }
+                for j in t + 1..n {
+                    if s[(t, j)] != 0 {
+                        let p = unimodular(s[(t, t)], s[(t, j)]);
+                        s.combine_columns(t, j, p);
+                        v.combine_columns(t, j, p);
+                    }
+                }
+                if (t + 1..m).any(|i| s[(i, t)] != 0) {
+                    continue;
+                }
+
+                let pivot = s[(t, t)];
+                match (t + 1..m).find(|&i| (t + 1..n).any(|j| s[(i, j)] % pivot != 0)) {
+                    Some(i) => {
+                        s.combine_rows(t, i, [[1, 1], [0, 1]]);
+                        u.combine_rows(t, i, [[1, 1], [0, 1]]);
+                    }
+                    None => break,
+                }
+            }
+
+            if s[(t, t)] < 0 {
+                s.negate_row(t);
+                u.negate_row(t);
+            }
+            debug!("d{} = {}", t + 1, s[(t, t)]);
+        }
+
+        (s, u, v)
+    }
+
+    /// The integer solutions of the linear Diophantine system `Ax = b`.
+    ///
+    /// - Output: a particular solution `x₀` and a basis `k₁, ..., kₛ` of the
+    ///   integer kernel, so that the solutions are `x₀ + Σ cᵢkᵢ` with
+    ///   `cᵢ ∈ Z`, or `None` if there is no integer solution.
+    ///
+    /// With `S = UAV` the system becomes `Sy = Ub` for `x = Vy`, which is
+    /// solvable if and only if `dᵢ | (Ub)ᵢ` for `i ≤ r` and `(Ub)ᵢ = 0` for
+    /// `i > r`. The last `n - r` columns of `V` span the kernel.
+    pub fn solve_diophantine(&self, b: &[Integer]) -> Option<(Vec<Integer>, Vec<Vec<Integer>>)> {
+        assert_eq!(self.rows, b.len(), "the dimensions do not match");
+
+        let (s, u, v) = self.smith_normal_form();
+        let c = u.apply(b);
+        let rank = (0..self.rows.min(self.cols))
+            .take_while(|&i| s[(i, i)] != 0)
+            .count();
+
+        if c[rank..].iter().any(|&c| c != 0) {
+            return None;
+        }
+        let mut y = vec![0; self.cols];
+        for i in 0..rank {
+            if c[i] % s[(i, i)] != 0 {
+                return None;
+            }
+            y[i] = c[i] / s[(i, i)];
+        }
+
+        Some((
+            v.apply(&y),
+            (rank..self.cols).map(|j| v.column(j)).collect(),
+        ))
+    }
+}
+
+impl<R: Ring> std::ops::Add for Matrix<R> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        assert_eq!(
+            (self.rows, self.cols),
+            (rhs.rows, rhs.cols),
+            "the dimensions do not match"
+        );
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            entries: self
+                .entries
+                .into_iter()
+                .zip(rhs.entries)
+                .map(|(a, b)| a + b)
+                .collect(),
+        }
+    }
+}
+impl<R: Ring> std::ops::Neg for Matrix<R> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Matrix {
+            rows: self.rows,
+            cols: self.cols,
+            entries: self.entries.into_iter().map(|c| -c).collect(),
+        }
+    }
+}
+impl<R: Ring> std::ops::Sub for Matrix<R> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+impl<R: Ring> std::ops::Mul for &Matrix<R> {
+    type Output = Matrix<R>;
+
+    /// The classical product with `rows · cols · n` multiplications.
+    fn mul(self, rhs: Self) -> Self::Output {
+        assert_eq!(self.cols, rhs.rows, "the dimensions do not match");
+
+        Matrix::from_fn(self.rows, rhs.cols, |i, j| {
+            (0..self.cols).fold(R::zero(), |sum, k| {
+                sum + self[(i, k)].clone() * rhs[(k, j)].clone()
+            })
+        })
+    }
+}
+impl<R: Ring> std::ops::Mul for Matrix<R> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{newton_interpolation::NewtonInterpolation, Integer, Rational};
+
+    use super::*;
+
+    fn rationals(rows: &[&[Integer]]) -> Matrix<Rational> {
+        Matrix::new(
+            rows.iter()
+                .map(|row| row.iter().copied().map(Rational::from).collect())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let b = a.transpose();
+
+        assert_eq!(b, Matrix::new(vec![vec![1, 4], vec![2, 5], vec![3, 6]]));
+        assert_eq!(&a * &b, Matrix::new(vec![vec![14, 32], vec![32, 77]]));
+        assert_eq!(&a * &Matrix::identity(3), a);
+        assert_eq!(a.apply(&[1, 0, -1]), [-2, -2]);
+        assert_eq!(a.clone() - a.clone(), Matrix::zero(2, 3));
+        assert_eq!(format!("{a:?}"), "[[1, 2, 3], [4, 5, 6]]");
+    }
+
+    #[test]
+    fn gaussian_elimination() {
+        let a = rationals(&[&[1, 2, 3], &[4, 5, 6], &[7, 8, 9]]);
+
+        assert_eq!(a.rank(), 2);
+        assert_eq!(a.determinant(), Rational::zero());
+        assert_eq!(a.inverse(), None);
+        let kernel = a.kernel();
+        assert_eq!(kernel.len(), 1);
+        assert!(a.apply(&kernel[0]).iter().all(|c| c.is_zero()));
+
+        let (r, pivots) = a.reduced_row_echelon_form();
+        assert_eq!(pivots, [0, 1]);
+        assert_eq!(r, rationals(&[&[1, 0, -1], &[0, 1, 2], &[0, 0, 0]]));
+
+        let b = rationals(&[&[0, 2, 1], &[1, 1, 0], &[3, 0, 1]]);
+        let inverse = b.inverse().unwrap();
+        assert_eq!(&b * &inverse, Matrix::identity(3));
+        assert_eq!(b.determinant(), Rational::from(-5));
+
+        let rhs = [1, 2, 3].map(Rational::from);
+        let v = b.solve(&rhs).unwrap();
+        assert_eq!(b.apply(&v), rhs);
+        assert_eq!(a.solve(&[0, 0, 1].map(Rational::from)), None);
+    }
+
+    #[test]
+    fn fraction_free_elimination() {
+        let a = Matrix::new(vec![
+            vec![2, -1, 0, 3],
+            vec![1, 4, -2, 0],
+            vec![0, 5, 1, -3],
+            vec![3, 0, 2, 1],
+        ]);
+        let det = Matrix::from_fn(4, 4, |i, j| Rational::from(a[(i, j)])).determinant();
+
+        assert_eq!(Rational::from(a.fraction_free_determinant()), det);
+        assert_eq!(
+            Matrix::new(vec![vec![0, 1], vec![1, 0]]).fraction_free_determinant(),
+            -1
+        );
+
+        let (r, pivots) =
+            Matrix::new(vec![vec![2, 4, 1], vec![1, 2, 3]]).fraction_free_echelon_form();
+        assert_eq!(pivots, [0, 2]);
+        assert_eq!(r, Matrix::new(vec![vec![2, 4, 1], vec![0, 0, 5]]));
+    }
+
+    #[test]
+    fn characteristic_polynomial() {
+        let a = rationals(&[&[1, 2], &[3, 4]]);
+        let chi = a.characteristic_polynomial();
+        assert_eq!(
+            chi,
+            Polynomial::new([-2, -5, 1].map(Rational::from).to_vec())
+        );
+
+        // Cayley–Hamilton
+        let a = rationals(&[&[2, 0, 1], &[1, -1, 3], &[0, 4, 1]]);
+        let chi = a.characteristic_polynomial();
+        assert_eq!(chi.evaluate_at(Rational::zero()), -a.determinant());
+        assert!(chi
+            .evaluate_with(&a, |c| Matrix::identity(3).scale(c))
+            .is_zero());
+    }
+
+    fn is_unimodular(u: &Matrix<Integer>) -> bool {
+        u.fraction_free_determinant().abs() == 1
+    }
+
+    #[test]
+    fn hermite_normal_form() {
+        let a = Matrix::new(vec![
+            vec![3, 3, 1, 4],
+            vec![0, 1, 0, 0],
+            vec![0, 0, 19, 16],
+            vec![0, 0, 0, 3],
+        ]);
+        let (h, u) = a.hermite_normal_form();
+
+        assert_eq!(&u * &a, h);
+        assert!(is_unimodular(&u));
+        assert_eq!(
+            h,
+            Matrix::new(vec![
+                vec![3, 0, 1, 1],
+                vec![0, 1, 0, 0],
+                vec![0, 0, 19, 1],
+                vec![0, 0, 0, 3],
+            ])
+        );
+
+        let a = Matrix::new(vec![vec![2, 4, 6], vec![-3, 1, 0], vec![-1, 5, 6]]);
+        let (h, u) = a.hermite_normal_form();
+        assert_eq!(&u * &a, h);
+        assert!(is_unimodular(&u));
+        assert_eq!(h.row(2), [0, 0, 0]);
+        assert!(h[(0, 0)] > 0 && h[(1, 1)] > 0 && h[(1, 0)] == 0);
+        assert!((0..h[(1, 1)]).contains(&h[(0, 1)]));
+    }
+
+    #[test]
+    fn smith_normal_form() {
+        let a = Matrix::new(vec![vec![2, 4, 4], vec![-6, 6, 12], vec![10, -4, -16]]);
+        let (s, u, v) = a.smith_normal_form();
+
+        assert_eq!(&(&u * &a) * &v, s);
+        assert!(is_unimodular(&u) && is_unimodular(&v));
+        assert_eq!(
+            s,
+            Matrix::new(vec![vec![2, 0, 0], vec![0, 6, 0], vec![0, 0, 12]])
+        );
+
+        let a = Matrix::new(vec![vec![6, 4], vec![10, 8], vec![4, 2]]);
+        let (s, u, v) = a.smith_normal_form();
+        assert_eq!(&(&u * &a) * &v, s);
+        assert_eq!(s, Matrix::new(vec![vec![2, 0], vec![0, 2], vec![0, 0]]));
+    }
+
+    #[test]
+    fn diophantine_systems() {
+        let a = Matrix::new(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let b = [6, 15];
+        let (x, kernel) = a.solve_diophantine(&b).unwrap();
+
+        assert_eq!(a.apply(&x), b);
+        assert_eq!(kernel.len(), 1);
+        assert_eq!(a.apply(&kernel[0]), [0, 0]);
+        let k = &kernel[0];
+        assert_eq!(k.iter().map(|c| c.abs()).collect::<Vec<_>>(), [1, 2, 1]);
+
+        // 6x + 10y + 15z = 1 since gcd(6, 10, 15) = 1, but 2x + 4y = 3 is
+        // not solvable
+        let a = Matrix::new(vec![vec![6, 10, 15]]);
+        let (x, kernel) = a.solve_diophantine(&[1]).unwrap();
+        assert_eq!(a.apply(&x), [1]);
+        assert_eq!(kernel.len(), 2);
+        assert_eq!(Matrix::new(vec![vec![2, 4]]).solve_diophantine(&[3]), None);
+        assert_eq!(
+            Matrix::new(vec![vec![1, 1], vec![1, 1]]).solve_diophantine(&[1, 2]),
+            None
+        );
+    }
+
+    #[test]
+    fn vandermonde() {
+        let samples = [(-5, -2), (-1, 6), (0, -1), (2, 3)]
+            .map(|(u, v)| (Rational::from(u), Rational::from(v)));
+        let us = samples.map(|(u, _)| u);
+        let vs = samples.map(|(_, v)| v);
+
+        let coefficients = Matrix::vandermonde(&us, 4).solve(&vs).unwrap();
+        assert_eq!(
+            Polynomial::new(coefficients),
+            NewtonInterpolation::run(&samples).unwrap()
+        );
+    }
+}
@@ -256,7 +256,10 @@ impl<F, O: MonomialOrder<F>> Monomial<F, O> {
     {
         self.map_coef(|_| F::identity())
     }
-    pub fn map_coef(&self, f: impl FnOnce(&F) -> F) -> Monomial<F, O> {
+    pub fn map_coef<G>(&self, f: impl FnOnce(&F) -> G) -> Monomial<G, O>
+    where
+        O: MonomialOrder<G>,
+    {
         match self {
             Monomial::Constant(coef) => Monomial::Constant(f(coef)),
             Monomial::Mono { ord, coef, powers } => Monomial::Mono {
@@ -179,6 +179,52 @@ where
             Self::Constant(c.into())
         }
     }
+
+    /// The polynomial over another coefficient ring with every coefficient
+    /// `c` replaced by `map(c)`.
+    pub fn map_coefficients<G>(&self, map: impl Fn(&F) -> G) -> MultivariatePolynomial<G, O>
+    where
+        G: Ring,
+        O: MonomialOrder<G>,
+    {
+        match self {
+            MultivariatePolynomial::Constant(c) => MultivariatePolynomial::Constant(map(c)),
+            MultivariatePolynomial::Terms { ord, terms } => MultivariatePolynomial::new(
+                ord.clone(),
+                terms.iter().map(|t| t.map_coef(&map)).collect(),
+            ),
+        }
+    }
+}
+
+/// Reduction modulo `N`.
+impl<O, const N: Natural> From<MultivariatePolynomial<Integer, O>>
+    for MultivariatePolynomial<Finite<N>, O>
+where
+    O: MonomialOrder<Integer> + MonomialOrder<Finite<N>>,
+{
+    fn from(f: MultivariatePolynomial<Integer, O>) -> Self {
+        f.map_coefficients(|c| Finite::from(*c))
+    }
+}
+/// Lifting to integer coefficients with the symmetric representatives of
+/// [`Finite::symmetric`].
+impl<O, const N: Natural> From<MultivariatePolynomial<Finite<N>, O>>
+    for MultivariatePolynomial<Integer, O>
+where
+    O: MonomialOrder<Integer> + MonomialOrder<Finite<N>>,
+{
+    fn from(f: MultivariatePolynomial<Finite<N>, O>) -> Self {
+        f.map_coefficients(|c| c.symmetric())
+    }
+}
+impl<O> From<MultivariatePolynomial<Integer, O>> for MultivariatePolynomial<Rational, O>
+where
+    O: MonomialOrder<Integer> + MonomialOrder<Rational>,
+{
+    fn from(f: MultivariatePolynomial<Integer, O>) -> Self {
+        f.map_coefficients(|c| Rational::from(*c))
+    }
 }
 
 impl<F, O> From<Monomial<F, O>> for MultivariatePolynomial<F, O>
@@ -359,3 +405,22 @@ where
         self * MultivariatePolynomial::constant(None, rhs)
     }
 }
+
+#[test]
+fn test_coefficient_conversions() {
+    use crate::mono::PLex;
+
+    let [x, y] = MultivariatePolynomial::<Integer, _>::init(PLex::default());
+    let f = 9 * x(2) * y(1) + -4 * y(3) + 7 * x(1);
+
+    let reduced = MultivariatePolynomial::<Finite<7>, _>::from(f.clone());
+    assert_eq!(format!("{reduced:?}"), "2x²y + 3y³");
+    assert_eq!(
+        format!("{:?}", MultivariatePolynomial::<Integer, _>::from(reduced)),
+        "2x²y + 3y³"
+    );
+    assert_eq!(
+        MultivariatePolynomial::<Rational, _>::from(f.clone()),
+        f.map_coefficients(|c| Rational::from(*c))
+    );
+}
@@ -12,3 +12,77 @@ impl Identity<Multiplication> for u128 {
         1
     }
 }
+
+/// `gcd(n, m)` by the Euclidean algorithm.
+pub fn gcd(mut n: Natural, mut m: Natural) -> Natural {
+    while m != 0 {
+        let tmp = n;
+        n = m;
+        m = tmp % m;
+    }
+    n
+}
+
+/// `a + b mod m` for residues `a, b < m`, which is reduced without forming
+/// the sum, as that may not fit in a [`Natural`].
+pub fn add_mod(a: Natural, b: Natural, m: Natural) -> Natural {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// `ab mod m` for `m > 0`, without overflowing even when the product `ab`
+/// does not fit in a [`Natural`].
+pub fn mul_mod(a: Natural, b: Natural, m: Natural) -> Natural {
+    assert!(m != 0, "the modulus must be nonzero");
+
+    let (a, b) = (a % m, b % m);
+    if let Some(ab) = a.checked_mul(b) {
+        return ab % m;
+    }
+
+    // Double and add.
+    let (mut a, mut b, mut result) = (a, b, 0);
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b >>= 1;
+    }
+    result
+}
+
+/// `aᵉ mod m` for `m > 0` by repeated squaring with [`mul_mod`].
+pub fn pow_mod(a: Natural, mut e: Natural, m: Natural) -> Natural {
+    let mut result = 1 % m;
+    let mut square = a % m;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul_mod(result, square, m);
+        }
+        e >>= 1;
+        if e > 0 {
+            square = mul_mod(square, square, m);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modular_arithmetic() {
+        // 2¹²⁸ - 159 is the largest prime below 2¹²⁸
+        let m = Natural::MAX - 158;
+        assert_eq!(mul_mod(m - 1, m - 1, m), 1);
+        assert_eq!(mul_mod(Natural::MAX, 2, m), 316);
+        assert_eq!(pow_mod(3, m - 1, m), 1);
+        assert_eq!(pow_mod(7, 0, 1), 0);
+        assert_eq!(pow_mod(2, 10, 1000), 24);
+    }
+}
@@ -1,10 +1,25 @@
-use std::{collections::HashSet, fmt::Debug, hash::Hash};
-
-use crate::{field::Field, polynomials::Polynomial, Group};
-
-pub struct NewtonInterpolation<'a, F: Field> {
-    samples: &'a [(F, F)],
-    stuff: Vec<Vec<Option<F>>>,
+use std::fmt::Debug;
+
+use tracing::debug;
+
+use crate::{field::Field, interpolation::Interpolator, polynomials::Polynomial, Group};
+
+/// Newton interpolation with divided differences, accepting samples one at a
+/// time.
+///
+/// After the samples `(u₀, v₀), ..., (uₙ₋₁, vₙ₋₁)` the interpolating
+/// polynomial is
+/// `f = c₀ + c₁(x - u₀) + ··· + cₙ₋₁(x - u₀)···(x - uₙ₋₂)` with the divided
+/// differences `cᵢ = f[u₀, ..., uᵢ]`. Pushing `(uₙ, vₙ)` only needs the
+/// diagonal `f[uⱼ, ..., uₙ₋₁]` of the previous last point, so it takes
+/// `O(n)` operations and leaves `c₀, ..., cₙ₋₁` unchanged.
+#[derive(Debug, Clone)]
+pub struct NewtonInterpolation<F> {
+    us: Vec<F>,
+    /// `f[uⱼ, ..., uₙ₋₁]` for `j = 0, ..., n - 1`.
+    diagonal: Vec<F>,
+    /// `cᵢ = f[u₀, ..., uᵢ]`.
+    coefficients: Vec<F>,
 }
 
 #[derive(Debug)]
@@ -12,139 +27,106 @@ pub enum NewtonInterpolationError {
     SamplesNotUnique,
 }
 
-impl<'a, F> NewtonInterpolation<'a, F>
-where
-    F: Field + Hash + Eq + Debug,
-{
-    fn u(&self, i: usize) -> F {
-        self.samples[i].0.clone()
-    }
-    fn v(&self, i: usize) -> F {
-        self.samples[i].1.clone()
-    }
-    fn set_f(&mut self, i: usize, j: usize, e: F) {
-        self.stuff[i][j] = Some(e);
-    }
-    fn f(&mut self, i: usize, j: usize) -> F {
-        if i == 0 {
-            return self.v(j);
-        }
-        if let Some(e) = self.stuff[i][j].clone() {
-            e
-        } else {
-            // let e = (self.f(i - 1, j) - self.f(i - 1, i - 1)) / (self.u(j) - self.u(i - 1));
-            let e = (self.f(i - 1, j) - self.f(i - 1, i - 1)) / (self.u(j) - self.u(i - 1));
-            self.stuff[i][j] = Some(e.clone());
-            e
-        }
+impl<F: Field + Debug> Default for NewtonInterpolation<F> {
+    fn default() -> Self {
+        Self::new()
     }
-    pub fn run(samples: &'a [(F, F)]) -> Result<Polynomial<F>, NewtonInterpolationError> {
-        let n = samples.len();
-
-        if n == 0 {
-            return Ok(Polynomial::zero());
-        }
-
-        let us: HashSet<_> = samples.iter().map(|(u, _)| u).collect();
+}
 
-        if samples.len() != us.len() {
-            return Err(NewtonInterpolationError::SamplesNotUnique);
+impl<F: Field + Debug> NewtonInterpolation<F> {
+    pub fn new() -> Self {
+        NewtonInterpolation {
+            us: vec![],
+            diagonal: vec![],
+            coefficients: vec![],
         }
+    }
 
-        let stuff: Vec<Vec<Option<F>>> = vec![vec![None; n]; n];
-
-        let mut ni = NewtonInterpolation { samples, stuff };
+    pub fn len(&self) -> usize {
+        self.us.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.us.is_empty()
+    }
 
-        for (i, (_u, v)) in samples.iter().enumerate() {
-            ni.stuff[0][i] = Some(v.clone());
-        }
+    /// The Newton coefficients `c₀, ..., cₙ₋₁`.
+    pub fn coefficients(&self) -> &[F] {
+        &self.coefficients
+    }
 
-        for i in 1..n {
-            for j in i..n {
-                assert!(ni.stuff[i][j].is_none());
-                println!(
-                    "f_{i}({j}) = (f_{is}({j}) - f_{is}({is})) / (u_{j} - u_{is}) = ({:?} - {:?}) / ({:?} - {:?}) = {:?}",
-                    ni.stuff[i - 1][j].clone().unwrap(), ni.stuff[i - 1][i - 1].clone().unwrap()
-                    , samples[j].0.clone(), samples[i - 1].0.clone(),
-                    (ni.stuff[i - 1][j].clone().unwrap() - ni.stuff[i - 1][i - 1].clone().unwrap())
-                        / (samples[j].0.clone() - samples[i - 1].0.clone()),
-                    is = i - 1,
-
-                );
-
-                ni.stuff[i][j] = Some(
-                    (ni.stuff[i - 1][j].clone().unwrap() - ni.stuff[i - 1][i - 1].clone().unwrap())
-                        / (samples[j].0.clone() - samples[i - 1].0.clone()),
-                );
+    /// Adds the sample `(u, v)`, computing the new diagonal
+    /// `f[uⱼ, ..., uₙ] = (f[uⱼ₊₁, ..., uₙ] - f[uⱼ, ..., uₙ₋₁]) / (uₙ - uⱼ)`
+    /// from `f[uₙ] = v` downwards.
+    pub fn push(&mut self, u: F, v: F) -> Result<(), NewtonInterpolationError> {
+        let n = self.len();
+
+        let mut diagonal = vec![v; n + 1];
+        for j in (0..n).rev() {
+            let d = u.clone() - self.us[j].clone();
+            if d.is_zero() {
+                return Err(NewtonInterpolationError::SamplesNotUnique);
             }
-        }
-        // n(n-1)/2 * 3
-
-        println!("{:?}", ni.stuff);
-
-        // let mut p = Polynomial::new(vec![ni.f(n - 1, n - 1)]);
-        let mut p = Polynomial::new(vec![ni.stuff[n - 1][n - 1].clone().unwrap()]);
-        println!("f := {p:?}");
-        for i in (0..n - 1).rev() {
-            // p = p.times_x(1) - p.scale(&ni.u(i)) + Polynomial::new(vec![ni.f(i, i)]);
-            let p1 = p.times_x(1) - p.scale(&ni.u(i))
-                + Polynomial::new(vec![ni.stuff[i][i].clone().unwrap()]);
-            println!(
-                "f := (x - {:?})({:?}) + {:?} = {p1:?}",
-                ni.u(i),
-                p,
-                &ni.stuff[i][i].clone().unwrap()
+            diagonal[j] = (diagonal[j + 1].clone() - self.diagonal[j].clone()) / d;
+            debug!(
+                "f[u{j}, ..., u{n}] = (f[u{}, ..., u{n}] - f[u{j}, ..., u{}]) / (u{n} - u{j}) = {:?}",
+                j + 1,
+                n - 1,
+                diagonal[j],
             );
-            p = p1;
-            // 2i
         }
-        // sum(i=0..n) 2i = n(n-1)/2 * 2 = n(n-1)
-        // n*(n-1)
 
-        // n(n-1)/2 * 3 + n*(n-1)
+        self.coefficients.push(diagonal[0].clone());
+        self.us.push(u);
+        self.diagonal = diagonal;
+        Ok(())
+    }
 
-        Ok(p)
+    /// The value of the current interpolating polynomial at `x`, by Horner's
+    /// rule in the Newton basis.
+    pub fn evaluate(&self, x: &F) -> F {
+        self.coefficients
+            .iter()
+            .zip(&self.us)
+            .rev()
+            .fold(F::zero(), |acc, (c, u)| {
+                acc * (x.clone() - u.clone()) + c.clone()
+            })
     }
-}
 
-fn newton<F, Map>(samples: &[F], map: Map) -> Polynomial<F>
-where
-    F: Field + Debug,
-    Map: Fn(usize) -> F,
-{
-    if samples.is_empty() {
-        return Polynomial::zero();
+    /// The current interpolating polynomial, by Horner's rule in the Newton
+    /// basis: `f := (x - uᵢ)f + cᵢ` for `i = n - 1, ..., 0`.
+    pub fn polynomial(&self) -> Polynomial<F> {
+        let mut p = Polynomial::zero();
+        for (i, (c, u)) in self.coefficients.iter().zip(&self.us).enumerate().rev() {
+            p = p.times_x(1) - p.scale(u) + Polynomial::new(vec![c.clone()]);
+            debug!("f := (x - u{i})f + {c:?} = {p:?}");
+        }
+        p
     }
 
-    // (x - u0)g = x*g - u0*g
-    let g = newton(
-        &samples[1..],
-        Box::new(|i: usize| (map(i + 1) - map(0)) / (samples[i + 1].clone() - samples[0].clone()))
-            as Box<dyn Fn(usize) -> F>,
-    );
+    pub fn run(samples: &[(F, F)]) -> Result<Polynomial<F>, NewtonInterpolationError> {
+        let scope = tracing::span!(
+            tracing::Level::DEBUG,
+            "Newton interpolation",
+            samples = format!("{samples:?}"),
+        );
+        let _enter = scope.enter();
 
-    g.times_x(1) - g.scale(&samples[0]) + Polynomial::new(vec![map(0)])
-}
+        let mut ni = NewtonInterpolation::new();
+        for (u, v) in samples {
+            ni.push(u.clone(), v.clone())?;
+        }
 
-fn newton_slice<F, Map>(samples: &[F], offset: usize, map: Map) -> Polynomial<F>
-where
-    F: Field + Debug,
-    Map: Fn(usize) -> F,
-{
-    if samples.len() == offset {
-        return Polynomial::zero();
+        Ok(ni.polynomial())
     }
+}
 
-    // (x - u0)g = x*g - u0*g
-    let g = newton_slice(
-        samples,
-        offset + 1,
-        Box::new(|i: usize| {
-            (map(i + 1) - map(0)) / (samples[offset + i + 1].clone() - samples[offset].clone())
-        }) as Box<dyn Fn(usize) -> F>,
-    );
+impl<F: Field + Debug> Interpolator<F> for NewtonInterpolation<F> {
+    type Error = NewtonInterpolationError;
 
-    g.times_x(1) - g.scale(&samples[offset]) + Polynomial::new(vec![map(0)])
+    fn interpolate(samples: &[(F, F)]) -> Result<Polynomial<F>, Self::Error> {
+        NewtonInterpolation::run(samples)
+    }
 }
 
 #[cfg(test)]
@@ -227,6 +209,34 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn incremental() -> Result<(), NewtonInterpolationError> {
+        let samples: [(Rational, Rational); 4] = [(-5, -2), (-1, 6), (0, -1), (2, 3)]
+            .map(|(u, v)| (Rational::from(u), Rational::from(v)));
+
+        let mut ni = NewtonInterpolation::new();
+        for (n, (u, v)) in samples.iter().enumerate() {
+            let before = ni.coefficients().to_vec();
+            ni.push(*u, *v)?;
+
+            assert_eq!(ni.len(), n + 1);
+            assert_eq!(ni.coefficients()[..n], before);
+            let f = ni.polynomial();
+            assert_eq!(f, NewtonInterpolation::run(&samples[..=n])?);
+            for x in [-3, 0, 7].map(Rational::from) {
+                assert_eq!(ni.evaluate(&x), f.evaluate_at(x));
+            }
+        }
+
+        assert!(matches!(
+            ni.push(Rational::from(-1), Rational::from(0)),
+            Err(NewtonInterpolationError::SamplesNotUnique)
+        ));
+        assert_eq!(ni.len(), samples.len());
+
+        Ok(())
+    }
+
     prop_compose! {
         fn samples()(n in 0..101usize)(us in prop::collection::vec(1..10u128, n), vs in prop::collection::vec(0..10u128, n)) -> Vec<(u128, u128)> {
             us.into_iter().scan(0, |state,x| {
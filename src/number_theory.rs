@@ -0,0 +1,440 @@
+//! # Number-theoretic functions
+
+use rand::{rngs::StdRng, SeedableRng};
+use tracing::debug;
+
+use crate::{
+    ch19::factor,
+    naturals::{add_mod, gcd, mul_mod, pow_mod},
+    Integer, Natural,
+};
+
+/// The distinct prime divisors of `n > 0`, in increasing order.
+pub fn prime_divisors(n: Natural) -> Vec<Natural> {
+    prime_powers(n).into_iter().map(|(p, _)| p).collect()
+}
+
+/// The prime powers `(p, e)` with `pᵉ ∥ n` for `n > 0`, from [`factor`].
+///
+/// The factorization is unique, so the randomness of Pollard's `ρ` only
+/// affects the running time, and a fixed seed keeps that reproducible.
+fn prime_powers(n: Natural) -> Vec<(Natural, u32)> {
+    factor(n, &mut StdRng::seed_from_u64(0))
+        .into_iter()
+        .map(|(p, e)| (p, e as u32))
+        .collect()
+}
+
+/// The positive divisors of `n > 0`, by trial division.
+pub fn divisors(n: Natural) -> Vec<Natural> {
+    let mut small = vec![];
+    let mut large = vec![];
+    let mut d = 1;
+    while d * d <= n {
+        if n.is_multiple_of(d) {
+            small.push(d);
+            if d * d != n {
+                large.push(n / d);
+            }
+        }
+        d += 1;
+    }
+    small.extend(large.into_iter().rev());
+    small
+}
+
+/// The Möbius function `μ(n)` for `n > 0`.
+pub fn mobius(n: Natural) -> Integer {
+    let divisors = prime_divisors(n);
+    if divisors.iter().product::<Natural>() != n {
+        0
+    } else if divisors.len().is_multiple_of(2) {
+        1
+    } else {
+        -1
+    }
+}
+
+/// Euler's totient `φ(n) = #(Z/nZ)^× = ∏ pᵉ⁻¹ (p - 1)` over `pᵉ ∥ n`, for
+/// `n > 0`.
+pub fn euler_phi(n: Natural) -> Natural {
+    prime_powers(n)
+        .into_iter()
+        .map(|(p, e)| p.pow(e - 1) * (p - 1))
+        .product()
+}
+
+/// Carmichael's function `λ(n)`, the exponent of `(Z/nZ)^×`, for `n > 0`.
+///
+/// It is the lcm of the `λ(pᵉ)` over `pᵉ ∥ n`, where `λ(pᵉ) = φ(pᵉ)` for odd
+/// `p` and for `2, 4`, and `λ(2ᵉ) = 2ᵉ⁻²` for `e ≥ 3`.
+pub fn carmichael_lambda(n: Natural) -> Natural {
+    prime_powers(n)
+        .into_iter()
+        .map(|(p, e)| match (p, e) {
+            (2, e) if e >= 3 => 1 << (e - 2),
+            (p, e) => p.pow(e - 1) * (p - 1),
+        })
+        .fold(1, |l, m| l / gcd(l, m) * m)
+}
+
+/// The Legendre symbol `(a/p)` for an odd prime `p`, which is `0` if
+/// `p | a`, `1` if `a` is a square modulo `p` and `-1` otherwise.
+pub fn legendre_symbol(a: Integer, p: Natural) -> Integer {
+    jacobi_symbol(a, p)
+}
+
+/// The Jacobi symbol `(a/n)` for odd `n > 0`, which is the Legendre symbol
+/// for prime `n`.
+///
+/// By quadratic reciprocity `(a/n) = (n/a)` unless `a ≡ n ≡ 3 mod 4`, and
+/// `(2/n) = -1` exactly for `n ≡ ±3 mod 8`, which gives a Euclidean-like
+/// algorithm.
+pub fn jacobi_symbol(a: Integer, n: Natural) -> Integer {
+    // reduce in Natural, since n may exceed Integer::MAX
+    let r = a.unsigned_abs() % n;
+    natural_jacobi_symbol(if a < 0 && r != 0 { n - r } else { r }, n)
+}
+
+/// The Jacobi symbol `(a/n)` for `a ∈ N`, as in [`jacobi_symbol`].
+pub fn natural_jacobi_symbol(a: Natural, n: Natural) -> Integer {
+    assert!(n % 2 == 1, "the Jacobi symbol needs an odd n > 0");
+
+    let mut a = a % n;
+    let mut n = n;
+    let mut sign = 1;
+    while a != 0 {
+        while a.is_multiple_of(2) {
+            a /= 2;
+            if n % 8 == 3 || n % 8 == 5 {
+                sign = -sign;
+            }
+        }
+        std::mem::swap(&mut a, &mut n);
+        if a % 4 == 3 && n % 4 == 3 {
+            sign = -sign;
+        }
+        a %= n;
+    }
+
+    if n == 1 {
+        sign
+    } else {
+        0
+    }
+}
+
+/// The multiplicative order of `a` modulo `n > 1`, or `None` if `a` is not
+/// a unit.
+///
+/// The order divides `λ(n)`, from which the prime factors are removed as
+/// long as `a` to the quotient is still `1`.
+pub fn multiplicative_order(a: Natural, n: Natural) -> Option<Natural> {
+    if gcd(a % n, n) != 1 {
+        return None;
+    }
+
+    let lambda = carmichael_lambda(n);
+    let mut order = lambda;
+    for q in prime_divisors(lambda) {
+        while order.is_multiple_of(q) && pow_mod(a, order / q, n) == 1 {
+            order /= q;
+        }
+    }
+    Some(order)
+}
+
+/// The least primitive root modulo `n > 0`, that is a generator of
+/// `(Z/nZ)^×`, or `None` if the group is not cyclic.
+///
+/// Primitive roots exist exactly for `n = 1, 2, 4, pᵉ, 2pᵉ` with odd primes
+/// `p`, and `g` is one if and only if `g^(φ(n)/q) ≠ 1` for all primes
+/// `q | φ(n)`.
+pub fn primitive_root(n: Natural) -> Option<Natural> {
+    let scope = tracing::span!(tracing::Level::DEBUG, "Primitive root", n = n.to_string());
+    let _enter = scope.enter();
+
+    let odd = prime_divisors(n >> n.trailing_zeros());
+    let cyclic = matches!((n.trailing_zeros(), odd.len()), (0..=2, 0) | (0 | 1, 1));
+    if !cyclic {
+        return None;
+    }
+    if n <= 2 {
+        return Some(n - 1);
+    }
+
+    let phi = euler_phi(n);
+    let primes = prime_divisors(phi);
+    (2..n).find(|&g| {
+        let primitive = gcd(g, n) == 1 && primes.iter().all(|q| pow_mod(g, phi / q, n) != 1);
+        debug!("g = {g}: primitive is {primitive}");
+        primitive
+    })
+}
+
+/// The least `z > 1` with `(z/p) = -1`, for an odd prime `p`.
+fn non_residue(p: Natural) -> Natural {
+    (2..p)
+        .find(|&z| natural_jacobi_symbol(z, p) == -1)
+        .expect("half of the units are non-residues")
+}
+
+/// The Tonelli–Shanks algorithm.
+///
+/// - Input: `a` and an odd prime `p`.
+/// - Output: some `r` with `r² ≡ a mod p`, the other one being `p - r`, or
+///   `None` if `a` is not a square modulo `p`.
+///
+/// With `p - 1 = 2ˢ q` for odd `q`, the candidate `r = a^((q+1)/2)` has the
+/// error `t = a^q = r²/a` in the Sylow 2-subgroup. It is corrected with
+/// powers of `c = z^q` for a non-residue `z`, which generates that subgroup,
+/// halving the order of `t` in each step.
+pub fn tonelli_shanks(a: Natural, p: Natural) -> Option<Natural> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "Tonelli–Shanks",
+        a = a.to_string(),
+        p = p.to_string(),
+    );
+    let _enter = scope.enter();
+
+    let a = a % p;
+    match natural_jacobi_symbol(a, p) {
+        0 => return Some(0),
+        -1 => return None,
+        _ => {}
+    }
+
+    // 1.
+    let mut s = (p - 1).trailing_zeros();
+    let q = (p - 1) >> s;
+    let mut c = pow_mod(non_residue(p), q, p);
+    let mut r = pow_mod(a, q.div_ceil(2), p);
+    let mut t = pow_mod(a, q, p);
+
+    // 2.
+    while t != 1 {
+        // the order 2ⁱ of t
+        let mut i = 0;
+        let mut square = t;
+        while square != 1 {
+            square = mul_mod(square, square, p);
+            i += 1;
+        }
+
+        let b = pow_mod(c, 1 << (s - i - 1), p);
+        r = mul_mod(r, b, p);
+        c = mul_mod(b, b, p);
+        t = mul_mod(t, c, p);
+        s = i;
+        debug!("r = {r}, t = {t} of order 2^{i} before");
+    }
+
+    Some(r)
+}
+
+/// Cipolla's algorithm.
+///
+/// - Input: `a` and an odd prime `p`.
+/// - Output: some `r` with `r² ≡ a mod p`, or `None` if `a` is not a
+///   square modulo `p`.
+///
+/// For `b` with `b² - a` a non-residue, `ω = √(b² - a)` generates
+/// `F_p² = F_p[ω]`, and `r = (b + ω)^((p+1)/2)` lies in `F_p` with `r² = a`,
+/// since `(b + ω)^p = b - ω` gives `(b + ω)^(p+1) = b² - ω² = a`.
+pub fn cipolla(a: Natural, p: Natural) -> Option<Natural> {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "Cipolla",
+        a = a.to_string(),
+        p = p.to_string(),
+    );
+    let _enter = scope.enter();
+
+    let a = a % p;
+    match natural_jacobi_symbol(a, p) {
+        0 => return Some(0),
+        -1 => return None,
+        _ => {}
+    }
+
+    // 1.
+    let (b, w) = (1..p)
+        .map(|b| (b, add_mod(mul_mod(b, b, p), p - a, p)))
+        .find(|(_, w)| natural_jacobi_symbol(*w, p) == -1)
+        .expect("half of the b give non-residues");
+    debug!("b = {b}, ω² = {w}");
+
+    // 2. (x₀ + x₁ω)(y₀ + y₁ω) = (x₀y₀ + x₁y₁ω²) + (x₀y₁ + x₁y₀)ω
+    let mul = |(x0, x1): (Natural, Natural), (y0, y1): (Natural, Natural)| {
+        (
+            add_mod(mul_mod(x0, y0, p), mul_mod(mul_mod(x1, y1, p), w, p), p),
+            add_mod(mul_mod(x0, y1, p), mul_mod(x1, y0, p), p),
+        )
+    };
+    let (mut result, mut square, mut e) = ((1, 0), (b, 1), p.div_ceil(2));
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul(result, square);
+        }
+        square = mul(square, square);
+        e >>= 1;
+    }
+    debug_assert_eq!(result.1, 0, "(b + ω)^((p+1)/2) lies in F_p");
+
+    Some(result.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{finite::with_prime, Finite, Ring};
+
+    use super::*;
+
+    /// The units of `Z/nZ` by brute force.
+    fn units(n: Natural) -> Vec<Natural> {
+        (1..n.max(2)).filter(|&a| gcd(a, n) == 1).collect()
+    }
+
+    #[test]
+    fn arithmetic_functions() {
+        for n in 1..300 {
+            let units = units(n);
+            assert_eq!(euler_phi(n), units.len() as Natural, "φ({n})");
+
+            let lambda = (1..)
+                .find(|&e| units.iter().all(|&a| pow_mod(a, e, n) == 1 % n || n == 1))
+                .unwrap();
+            assert_eq!(carmichael_lambda(n), lambda, "λ({n})");
+
+            assert_eq!(
+                divisors(n),
+                (1..=n).filter(|d| n.is_multiple_of(*d)).collect::<Vec<_>>()
+            );
+            // Σ_{d | n} μ(d) = [n = 1]
+            let sum: Integer = divisors(n).into_iter().map(mobius).sum();
+            assert_eq!(sum, (n == 1) as Integer);
+        }
+        assert_eq!(carmichael_lambda(561), 80);
+        assert_eq!(prime_divisors(2 * 2 * 3 * 101), [2, 3, 101]);
+        // a prime factor above 2⁶⁴ is out of reach for trial division
+        let p = (1 << 64) + 13;
+        assert_eq!(prime_divisors(4 * p), [2, p]);
+        assert_eq!(euler_phi(4 * p), 2 * (p - 1));
+    }
+
+    #[test]
+    fn jacobi_symbols() {
+        // Euler's criterion for the Legendre symbol
+        for p in [3, 5, 7, 11, 13, 101] {
+            for a in -20i128..20 {
+                let euler = match pow_mod(a.rem_euclid(p as Integer) as Natural, (p - 1) / 2, p) {
+                    0 => 0,
+                    1 => 1,
+                    _ => -1,
+                };
+                assert_eq!(legendre_symbol(a, p), euler, "({a}/{p})");
+            }
+        }
+        // multiplicative in n
+        for a in -30..30 {
+            assert_eq!(
+                jacobi_symbol(a, 3 * 5 * 7),
+                jacobi_symbol(a, 3) * jacobi_symbol(a, 5) * jacobi_symbol(a, 7)
+            );
+        }
+        assert_eq!(jacobi_symbol(1001, 9907), -1);
+
+        // the prime 2¹²⁸ - 159 ≡ 1 mod 4 exceeds Integer::MAX
+        let p = Natural::MAX - 158;
+        assert_eq!(jacobi_symbol(1 << 100, p), 1);
+        assert_eq!(jacobi_symbol(-(1 << 100), p), 1);
+        assert_eq!(natural_jacobi_symbol(p - (1 << 100), p), 1);
+    }
+
+    #[test]
+    fn orders_and_primitive_roots() {
+        for n in 2..200 {
+            for a in 0..n {
+                let brute =
+                    (gcd(a, n) == 1).then(|| (1..).find(|&e| pow_mod(a, e, n) == 1).unwrap());
+                assert_eq!(multiplicative_order(a, n), brute, "ord_{n}({a})");
+            }
+
+            let phi = euler_phi(n);
+            let brute = (1..n).find(|&g| multiplicative_order(g, n) == Some(phi));
+            assert_eq!(primitive_root(n), brute, "primitive root modulo {n}");
+        }
+        assert_eq!(primitive_root(1), Some(0));
+        assert_eq!(primitive_root(23), Some(5));
+    }
+
+    /// The square roots of `a` in `F_p` by brute force.
+    fn brute_force_roots<const P: Natural>(a: Natural) -> Vec<Natural> {
+        (0..P)
+            .filter(|&x| Finite::<P>::from(x).pow(2) == Finite::from(a))
+            .collect()
+    }
+
+    fn check_roots<const P: Natural>() {
+        for a in 0..P {
+            let roots = brute_force_roots::<P>(a);
+            for r in [tonelli_shanks(a, P), cipolla(a, P)] {
+                match r {
+                    Some(r) => assert!(roots.contains(&r), "√{a} mod {P}"),
+                    None => assert!(roots.is_empty(), "√{a} mod {P}"),
+                }
+            }
+            assert_eq!(
+                legendre_symbol(a as Integer, P),
+                match roots.len() {
+                    0 => -1,
+                    1 => 0,
+                    _ => 1,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn square_roots() {
+        for p in [23, 97, 257 as Natural] {
+            with_prime!(p, P => check_roots::<P>());
+        }
+
+        for p in [65537, 1048583 as Natural] {
+            for a in (0..p).step_by(997) {
+                let r = tonelli_shanks(a, p);
+                assert_eq!(r.is_some(), legendre_symbol(a as Integer, p) != -1);
+                assert_eq!(r.map(|r| r.min(p - r)), cipolla(a, p).map(|r| r.min(p - r)));
+                if let Some(r) = r {
+                    assert_eq!(mul_mod(r, r, p), a);
+                }
+            }
+        }
+
+        // the primes 2¹²⁸ - 159 ≡ 1 mod 8 and 2⁶⁴ + 13 ≡ 5 mod 8, where products
+        // overflow
+        for p in [Natural::MAX - 158, (1 << 64) + 13] {
+            for a in [2, 3, 5, 1 << 100] {
+                let r = tonelli_shanks(a, p);
+                assert_eq!(r.is_some(), jacobi_symbol(a as Integer, p) == 1);
+                assert_eq!(r.map(|r| r.min(p - r)), cipolla(a, p).map(|r| r.min(p - r)));
+                if let Some(r) = r {
+                    assert_eq!(mul_mod(r, r, p), a % p);
+                }
+            }
+            for r in [3, 1 << 100, p - 2] {
+                let a = mul_mod(r, r, p);
+                for s in [tonelli_shanks(a, p), cipolla(a, p)] {
+                    let s = s.expect("a = r² is a square");
+                    assert!(s == r % p || s == p - r % p, "√{a} mod {p}");
+                }
+            }
+        }
+        assert_eq!(
+            Finite::<97>::from(tonelli_shanks(2, 97).unwrap()).pow(2),
+            Finite::from(2i128)
+        );
+    }
+}
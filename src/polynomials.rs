@@ -1,11 +1,12 @@
 use itertools::Itertools;
 
 use crate::{
+    ch03, ch09,
     euclidean_domain::EuclideanDomain,
     field::Field,
     group::AbelianGroup,
     identity::{Addition, Identity, Multiplication},
-    Group, Natural, Ring,
+    Finite, Group, Integer, Natural, Rational, Ring,
 };
 
 #[derive(Clone)]
@@ -23,12 +24,15 @@ where
     F: PartialEq + Identity<Addition>,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.deg() == other.deg()
-            && self
-                .coefficients
-                .iter()
-                .zip(&other.coefficients)
-                .all(|(a, b)| a == b)
+        self.coefficients
+            .iter()
+            .zip_longest(&other.coefficients)
+            .all(|cs| match cs {
+                itertools::EitherOrBoth::Both(a, b) => a == b,
+                itertools::EitherOrBoth::Left(c) | itertools::EitherOrBoth::Right(c) => {
+                    c.is_identity()
+                }
+            })
     }
 }
 impl<F> Eq for Polynomial<F> where F: PartialEq + Identity<Addition> {}
@@ -152,6 +156,40 @@ where
         Some((Polynomial { coefficients: q }, r))
     }
 
+    /// Pseudo-division of `self` by `rhs`, that is `q, r` with
+    /// `lc(rhs)^(n - m + 1) · self = q · rhs + r` and `deg r < m`, where `n`
+    /// and `m` are the degrees of `self` and `rhs`. Unlike [`div_rem`] this
+    /// requires no divisions in `F`.
+    ///
+    /// [`div_rem`]: Polynomial::div_rem
+    pub fn pseudo_div_rem(&self, rhs: &Self) -> (Self, Self)
+    where
+        F: Ring,
+    {
+        let n = self.deg();
+        let m = rhs.deg();
+
+        if n < m {
+            return (Polynomial::zero(), self.clone());
+        }
+
+        let b = rhs.lc();
+        let mut q = Polynomial::zero();
+        let mut r = self.clone();
+
+        for i in (0..=(n - m)).rev() {
+            let t = if r.deg() == m + i {
+                Polynomial::new(vec![r.lc()]).times_x(i)
+            } else {
+                Polynomial::zero()
+            };
+            q = q.scale(&b) + t.clone();
+            r = r.scale(&b) - t * rhs;
+        }
+
+        (q.normalized(), r.normalized())
+    }
+
     pub fn lc(&self) -> F
     where
         F: Group,
@@ -169,10 +207,8 @@ where
         let mut new = Polynomial {
             coefficients: self.coefficients.clone(),
         };
-
-        for _ in 0..pow {
-            new.coefficients.insert(0, F::zero());
-        }
+        new.coefficients
+            .splice(0..0, std::iter::repeat_n(F::zero(), pow as usize));
 
         new.normalized()
     }
@@ -243,6 +279,14 @@ where
         Self::new(coefs)
     }
 
+    /// The polynomial `Σ map(fᵢ) xⁱ` over another coefficient ring.
+    pub fn map_coefficients<G>(&self, map: impl FnMut(&F) -> G) -> Polynomial<G>
+    where
+        G: Identity<Addition>,
+    {
+        Polynomial::new(self.coefficients.iter().map(map).collect())
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = (&F, Natural)> {
         self.coefficients
             .iter()
@@ -259,6 +303,235 @@ where
             .cloned()
             .unwrap_or_else(|| <F as Identity<Addition>>::identity())
     }
+
+    /// The content `cont(f)`, that is the gcd of the coefficients of `f`
+    /// with the sign of `lc(f)`, such that `pp(f) = f / cont(f)` has a
+    /// leading coefficient in normal form.
+    pub fn content(&self) -> F
+    where
+        F: EuclideanDomain,
+    {
+        let gcd = self
+            .coefficients
+            .iter()
+            .fold(F::zero(), |a, b| ch03::gcd(&a, b));
+
+        gcd * self.lc().lu()
+    }
+
+    /// The primitive part `pp(f) = f / cont(f)`.
+    pub fn primitive_part(&self) -> Self
+    where
+        F: EuclideanDomain,
+    {
+        if self.is_zero() {
+            return Polynomial::zero();
+        }
+
+        let content = self.content();
+        Polynomial::new(
+            self.coefficients
+                .iter()
+                .map(|c| c.clone() / content.clone())
+                .collect(),
+        )
+    }
+
+    /// Evaluates `f(x)` by Horner's rule at an `x` of another type `R`, into
+    /// which the coefficients are mapped by `embed`.
+    ///
+    /// Only addition and multiplication are required of `R`, so this also
+    /// evaluates at square matrices with `embed(c) = cI`, or at polynomials as
+    /// in [`compose`](Polynomial::compose).
+    pub fn evaluate_with<R>(&self, x: &R, embed: impl Fn(&F) -> R) -> R
+    where
+        F: Clone,
+        R: Clone + std::ops::Add<Output = R> + std::ops::Mul<Output = R>,
+    {
+        let mut coefficients = self.coefficients.iter().rev();
+        let lc = coefficients
+            .next()
+            .map(&embed)
+            .unwrap_or_else(|| embed(&F::identity()));
+
+        coefficients.fold(lc, |sum, c| sum * x.clone() + embed(c))
+    }
+
+    /// The composition `(f ∘ g)(x) = f(g(x))`, by Horner's rule.
+    pub fn compose(&self, g: &Self) -> Self
+    where
+        F: Ring,
+    {
+        self.evaluate_with(g, |c| Polynomial::new(vec![c.clone()]))
+    }
+
+    /// The Taylor shift `f(x + a)`.
+    ///
+    /// Splitting `f = f₀ + x^k f₁` with `k` the largest power of two below
+    /// `n = deg f + 1` gives `f(x + a) = f₀(x + a) + (x + a)^k f₁(x + a)`, so
+    /// divide and conquer with the precomputed `(x + a)^(2^i)` takes
+    /// `O(M(n) log n)` operations.
+    pub fn taylor_shift(&self, a: F) -> Self
+    where
+        F: Ring,
+    {
+        fn shift<F: Ring>(f: &[F], powers: &[Polynomial<F>]) -> Polynomial<F> {
+            if f.len() <= 1 {
+                return Polynomial::new(f.to_vec());
+            }
+
+            let i = (f.len() - 1).ilog2() as usize;
+            let (f0, f1) = f.split_at(1 << i);
+            shift(f0, powers) + powers[i].clone() * shift(f1, powers)
+        }
+
+        let mut powers = vec![Polynomial::new(vec![a, F::one()])];
+        while 1 << powers.len() <= self.deg() {
+            let last = powers.last().unwrap().clone();
+            powers.push(last.clone() * last);
+        }
+
+        // trailing zeros would split off more powers than `deg f` needs
+        shift(&self.normalized().coefficients, &powers)
+    }
+
+    /// Functional decomposition `f = g ∘ h` with `deg h = s`.
+    ///
+    /// For `f` of degree `n = rs` over a field in which `r` is invertible,
+    /// there is at most one such decomposition with `h` monic and
+    /// `h(0) = 0`. Since `h^r` agrees with the monic `f/lc(f)` in the
+    /// coefficients of `x^(n-1), ..., x^(n-s+1)`, these determine `h` one
+    /// coefficient at a time, and `g` is the `h`-adic expansion of `f`, which
+    /// must have constant digits.
+    pub fn decompose_with_degree(&self, s: Natural) -> Option<(Self, Self)>
+    where
+        F: Field + std::fmt::Debug,
+    {
+        let n = self.deg();
+        if s == 0 || !n.is_multiple_of(s) {
+            return None;
+        }
+        let r = n / s;
+        let r_inv = (0..r)
+            .map(|_| F::one())
+            .reduce(|a, b| a + b)?
+            .multiplicative_inverse()?;
+        let lc_inv = self.lc().multiplicative_inverse()?;
+
+        // 1.
+        let mut h = Polynomial::x().pow(s);
+        for j in 1..s {
+            let c =
+                (self.coef_at(n - j) * lc_inv.clone() - h.pow(r).coef_at(n - j)) * r_inv.clone();
+            h = h + Polynomial::x().pow(s - j).scale(&c);
+        }
+
+        // 2.
+        let mut digits = vec![];
+        let mut rest = self.clone();
+        while !rest.is_zero() {
+            let (q, c) = if rest.deg() < s {
+                (Polynomial::zero(), rest)
+            } else {
+                rest.div_rem(&h)?
+            };
+            if c.deg() > 0 {
+                return None;
+            }
+            digits.push(c.coef_at(0));
+            rest = q.normalized();
+        }
+
+        Some((Polynomial::new(digits), h))
+    }
+
+    /// A nontrivial functional decomposition `f = g ∘ h` with
+    /// `1 < deg h < deg f` as small as possible, if there is one, see
+    /// [`decompose_with_degree`](Polynomial::decompose_with_degree).
+    pub fn decompose(&self) -> Option<(Self, Self)>
+    where
+        F: Field + std::fmt::Debug,
+    {
+        (2..self.deg()).find_map(|s| self.decompose_with_degree(s))
+    }
+}
+
+/// The degree from which [`Polynomial::pow_mod`] reduces with Newton
+/// iteration instead of classical division.
+const NEWTON_DIVISION_THRESHOLD: Natural = 32;
+
+impl<F: Field + std::fmt::Debug> Polynomial<F> {
+    /// Modular exponentiation `selfᵉ rem f` by repeated squaring, for
+    /// nonzero `f`.
+    ///
+    /// For `f` of large degree `n` each product of degree `≤ 2n - 2` is
+    /// reduced as in Algorithm 9.5, with the inverse of `rev(f)` modulo
+    /// `x^(n-1)` computed by Newton iteration once for all reductions.
+    pub fn pow_mod(&self, mut e: Natural, f: &Self) -> Self {
+        assert!(!f.is_zero(), "division by zero");
+
+        let f = f.scale(&(F::one() / f.lc()));
+        let n = f.deg();
+        let inverse = (n >= NEWTON_DIVISION_THRESHOLD)
+            .then(|| ch09::inversion_newton_iteration(f.rev(n), n - 1));
+        let reduce = |a: Self| match &inverse {
+            Some(inverse) if (n..=2 * n - 2).contains(&a.deg()) => {
+                let m = a.deg() - n;
+                let q = (a.rev(a.deg()) * inverse).rem_pow(m + 1).rev(m);
+                a - &f * &q
+            }
+            _ => a % f.clone(),
+        };
+
+        let mut result = reduce(Polynomial::one());
+        let mut square = reduce(self.clone());
+        while e > 0 {
+            if e & 1 == 1 {
+                result = reduce(result * &square);
+            }
+            e >>= 1;
+            if e > 0 {
+                square = reduce(&square * &square);
+            }
+        }
+        result
+    }
+}
+
+impl Polynomial<Integer> {
+    /// The max-norm `‖f‖∞`, that is the largest absolute value of a
+    /// coefficient.
+    pub fn max_norm(&self) -> Natural {
+        self.coefficients
+            .iter()
+            .map(|c| c.unsigned_abs())
+            .max()
+            .unwrap_or(0)
+    }
+    /// The one-norm `‖f‖₁`, that is the sum of the absolute values of the
+    /// coefficients.
+    pub fn one_norm(&self) -> Natural {
+        self.coefficients.iter().map(|c| c.unsigned_abs()).sum()
+    }
+}
+
+/// Reduction modulo `N`.
+impl<const N: Natural> From<Polynomial<Integer>> for Polynomial<Finite<N>> {
+    fn from(f: Polynomial<Integer>) -> Self {
+        f.map_coefficients(|c| Finite::from(*c))
+    }
+}
+/// Lifting to `Z[x]` with the symmetric representatives of
+/// [`Finite::symmetric`].
+impl<const N: Natural> From<Polynomial<Finite<N>>> for Polynomial<Integer> {
+    fn from(f: Polynomial<Finite<N>>) -> Self {
+        f.map_coefficients(|c| c.symmetric())
+    }
+}
+impl From<Polynomial<Integer>> for Polynomial<Rational> {
+    fn from(f: Polynomial<Integer>) -> Self {
+        f.map_coefficients(|c| Rational::from(*c))
+    }
 }
 
 impl<F> std::ops::Mul for Polynomial<F>
@@ -373,7 +646,11 @@ where
     type Output = Self;
 
     fn div(self, rhs: Self) -> Self::Output {
-        self.div_rem(&rhs).unwrap().0.normalized()
+        if self.deg() < rhs.deg() {
+            Polynomial::zero()
+        } else {
+            self.div_rem(&rhs).unwrap().0.normalized()
+        }
     }
 }
 impl<F> std::ops::Sub<F> for Polynomial<F>
@@ -422,7 +699,11 @@ where
     F: Ring,
 {
     fn multiplicative_inverse(&self) -> Option<Self> {
-        None
+        if self.is_zero() || self.deg() > 0 {
+            None
+        } else {
+            Some(Polynomial::new(vec![self.lc().multiplicative_inverse()?]))
+        }
     }
 }
 
@@ -430,11 +711,21 @@ impl<F: Field + std::fmt::Debug> EuclideanDomain for Polynomial<F> {
     fn d(&self) -> Option<Natural> {
         Some(self.deg())
     }
+    fn lu(&self) -> Self {
+        if self.is_zero() {
+            Polynomial::one()
+        } else {
+            Polynomial::new(vec![self.lc()])
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{ch03::ExtendedEuclideanAlgorithm, Group, Polynomial, Rational};
+    use crate::{
+        ch03::ExtendedEuclideanAlgorithm, matrix::Matrix, Finite, Group, Integer, Polynomial,
+        Rational, Ring,
+    };
     use proptest::prelude::*;
 
     prop_compose! {
@@ -479,4 +770,118 @@ mod test {
 
         dbg!(a.div_rem(&b));
     }
+
+    proptest! {
+        #[test]
+        fn taylor_shift_is_composition(f in polynomial(), a in -5..5i128) {
+            let x_plus_a = Polynomial::new(vec![a.into(), 1.into()]);
+
+            prop_assert_eq!(f.taylor_shift(a.into()), f.compose(&x_plus_a));
+        }
+    }
+
+    fn poly(cs: &[Integer]) -> Polynomial<Integer> {
+        Polynomial::new(cs.to_vec())
+    }
+
+    #[test]
+    fn composition() {
+        let f = poly(&[1, 0, 1]);
+        let g = poly(&[2, 3]);
+
+        assert_eq!(f.compose(&g), poly(&[5, 12, 9]));
+        assert_eq!(g.compose(&f), poly(&[5, 0, 3]));
+        assert_eq!(
+            f.compose(&g).evaluate_at(4),
+            f.evaluate_at(g.evaluate_at(4))
+        );
+    }
+
+    #[test]
+    fn taylor_shift() {
+        let f = poly(&[7, -1, 0, 2, 0, 0, 3, 1, -5, 2]);
+
+        for a in [-3, 0, 1, 5] {
+            assert_eq!(f.taylor_shift(a), f.compose(&poly(&[a, 1])));
+        }
+        assert_eq!(poly(&[4]).taylor_shift(2), poly(&[4]));
+
+        let unnormalized = Polynomial {
+            coefficients: vec![1, 2, 0, 0, 0],
+        };
+        assert_eq!(unnormalized.taylor_shift(3), poly(&[7, 2]));
+    }
+
+    #[test]
+    fn evaluation_in_other_rings() {
+        let f = poly(&[3, -2, 5]);
+        let x = Finite::<7>::from(4i128);
+
+        assert_eq!(
+            f.evaluate_with(&x, |c| Finite::from(*c)),
+            Finite::from(f.evaluate_at(4))
+        );
+
+        // Cayley–Hamilton: a matrix is a root of its characteristic polynomial
+        let a = Matrix::new(vec![vec![1, 2], vec![3, 4]]);
+        let chi = poly(&[-2, -5, 1]);
+        assert_eq!(
+            chi.evaluate_with(&a, |c| Matrix::identity(2).scale(c)),
+            Matrix::zero(2, 2)
+        );
+    }
+
+    #[test]
+    fn decomposition() {
+        let poly =
+            |cs: &[Integer]| Polynomial::new(cs.iter().copied().map(Rational::from).collect());
+        let g = poly(&[1, -2, 0, 3]);
+        let h = poly(&[0, 5, 1]);
+        let f = g.compose(&h);
+
+        assert_eq!(f.decompose_with_degree(2), Some((g.clone(), h.clone())));
+        assert_eq!(f.decompose(), Some((g, h)));
+        assert_eq!(f.decompose_with_degree(3), None);
+
+        // x⁴ + x is indecomposable
+        assert_eq!(poly(&[0, 1, 0, 0, 1]).decompose(), None);
+    }
+
+    #[test]
+    fn modular_exponentiation() {
+        let poly = |cs: &[Integer]| {
+            Polynomial::<Finite<7>>::new(cs.iter().map(|c| Finite::from(*c)).collect())
+        };
+        let g = poly(&[1, 1, 0, 0, 0, 3]);
+
+        // classical and Newton division
+        for f in [poly(&[1, 1, 0, 2]), poly(&[1, 1]) + poly(&[3]).times_x(40)] {
+            for e in [0, 1, 2, 37, 50] {
+                assert_eq!(g.pow_mod(e, &f), g.pow(e) % f.clone(), "e = {e}, f = {f:?}");
+            }
+        }
+
+        // x^(7³) ≡ x mod x³ - 2 since x³ - 2 is irreducible over F₇
+        let f = poly(&[-2, 0, 0, 1]);
+        assert_eq!(Polynomial::x().pow_mod(7 * 7 * 7, &f), Polynomial::x());
+        assert_eq!(Finite::<101>::from(3i128).pow(100), Finite::one());
+    }
+
+    #[test]
+    fn conversions() {
+        let f = poly(&[-8, 3, 0, 13]);
+
+        let reduced = Polynomial::<Finite<7>>::from(f.clone());
+        assert_eq!(
+            reduced,
+            Polynomial::new([6i128, 3, 0, 6].map(Finite::from).to_vec())
+        );
+        assert_eq!(Polynomial::<Integer>::from(reduced), poly(&[-1, 3, 0, -1]));
+
+        assert_eq!(
+            Polynomial::<Rational>::from(f.clone()),
+            f.map_coefficients(|c| Rational::from(*c))
+        );
+        assert_eq!(f.map_coefficients(|c| c % 2), poly(&[0, 1, 0, 1]));
+    }
 }
@@ -2,6 +2,7 @@ use crate::{
     field::Field,
     group::AbelianGroup,
     identity::{Addition, Identity, Multiplication},
+    naturals::gcd,
     Group, Integer, Natural, Ring,
 };
 
@@ -9,7 +10,7 @@ pub fn rational(f: f64) -> Rational {
     Rational::approximate(f)
 }
 
-#[derive(Clone, Copy, Eq, PartialOrd)]
+#[derive(Clone, Copy, Eq)]
 pub struct Rational {
     pub num: Integer,
     pub denom: Natural,
@@ -17,12 +18,23 @@ pub struct Rational {
 
 impl PartialEq for Rational {
     fn eq(&self, other: &Self) -> bool {
-        self.num as f64 / self.denom as f64 == other.num as f64 / other.denom as f64
+        self.cmp(other).is_eq()
+    }
+}
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (*self - *other).num.cmp(&0)
     }
 }
 impl std::hash::Hash for Rational {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        (((self.num as f64 / self.denom as f64) * 1000000.0) as i128).hash(state);
+        let normalized = self.normalized();
+        (normalized.num, normalized.denom).hash(state);
     }
 }
 
@@ -67,21 +79,17 @@ impl Rational {
             denom: self.denom,
         }
     }
+    /// The nearest integer `⌊self + 1/2⌋`.
+    pub fn round(self) -> Integer {
+        let denom = self.denom as Integer;
+        (2 * self.num + denom).div_euclid(2 * denom)
+    }
     pub fn normalized(self) -> Self {
         if self.num.is_zero() {
             return Self::zero();
         }
 
-        let mut n = self.num.unsigned_abs();
-        let mut m = self.denom;
-
-        while m != 0 {
-            let tmp = n;
-            n = m;
-            m = tmp % m;
-        }
-
-        let gcd = n;
+        let gcd = gcd(self.num.unsigned_abs(), self.denom);
 
         Rational {
             num: self.num / Integer::from(gcd as i128),
@@ -101,6 +109,34 @@ fn rational_signed_normalize() {
     assert_eq!(rational(1.), minus_1 / minus_1);
 }
 
+#[test]
+fn rational_large_denominators() {
+    // the naive denominator 2¹²⁸ and numerator 2²⁰⁰ overflow
+    let x = Rational {
+        num: 1,
+        denom: 1 << 64,
+    };
+    assert_eq!(
+        x + x,
+        Rational {
+            num: 1,
+            denom: 1 << 63,
+        }
+    );
+    assert_eq!(x - x, Rational::zero());
+
+    let y = Rational {
+        num: 1 << 100,
+        denom: (1 << 100) + 1,
+    };
+    let z = Rational {
+        num: (1 << 100) + 1,
+        denom: 1 << 100,
+    };
+    assert_eq!(y * z, rational(1.));
+    assert_eq!(y / y, rational(1.));
+}
+
 impl From<i128> for Rational {
     fn from(n: i128) -> Self {
         Rational {
@@ -113,10 +149,14 @@ impl From<i128> for Rational {
 impl std::ops::Add for Rational {
     type Output = Self;
 
+    /// Over the common denominator `lcm(b, d)` rather than `bd`, which keeps
+    /// the intermediate products small.
     fn add(self, rhs: Self) -> Self::Output {
+        let g = gcd(self.denom, rhs.denom);
+        let (b, d) = (self.denom / g, rhs.denom / g);
         Rational {
-            num: self.num * rhs.denom as i128 + self.denom as i128 * rhs.num,
-            denom: self.denom * rhs.denom,
+            num: self.num * d as i128 + b as i128 * rhs.num,
+            denom: b * rhs.denom,
         }
         .normalized()
     }
@@ -125,11 +165,7 @@ impl std::ops::Sub for Rational {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        Rational {
-            num: self.num * rhs.denom as i128 - self.denom as i128 * rhs.num,
-            denom: self.denom * rhs.denom,
-        }
-        .normalized()
+        self + (-rhs)
     }
 }
 impl std::ops::Neg for Rational {
@@ -154,24 +190,27 @@ impl std::ops::Div for Rational {
     type Output = Self;
 
     fn div(self, rhs: Self) -> Self::Output {
-        Rational {
-            num: if rhs.num == rhs.num.abs() {
-                self.num * rhs.denom as i128
+        self * Rational {
+            num: if rhs.num < 0 {
+                -(rhs.denom as i128)
             } else {
-                -self.num * rhs.denom as i128
+                rhs.denom as i128
             },
-            denom: self.denom * rhs.num.unsigned_abs(),
+            denom: rhs.num.unsigned_abs(),
         }
-        .normalized()
     }
 }
 impl std::ops::Mul for Rational {
     type Output = Self;
 
+    /// Cancels `gcd(a, d)` and `gcd(c, b)` in `a/b · c/d` before
+    /// multiplying.
     fn mul(self, rhs: Self) -> Self::Output {
+        let g = gcd(self.num.unsigned_abs(), rhs.denom).max(1);
+        let h = gcd(rhs.num.unsigned_abs(), self.denom).max(1);
         Rational {
-            num: self.num * rhs.num,
-            denom: self.denom * rhs.denom,
+            num: (self.num / g as i128) * (rhs.num / h as i128),
+            denom: (self.denom / h) * (rhs.denom / g),
         }
         .normalized()
     }
@@ -17,13 +17,21 @@ pub trait Ring: AbelianGroup + std::ops::Mul<Output = Self> + Identity<Multiplic
     }
     /// The multiplicative inverse in the case that `self` is a unit
     fn multiplicative_inverse(&self) -> Option<Self>;
-    fn pow(&self, pow: Natural) -> Self
+    /// `self^pow` by repeated squaring, using `O(log pow)` multiplications.
+    fn pow(&self, mut pow: Natural) -> Self
     where
         Self: Clone,
     {
         let mut total = Ring::one();
-        for _ in 0..pow {
-            total = total * self.clone();
+        let mut square = self.clone();
+        while pow > 0 {
+            if pow & 1 == 1 {
+                total = total * square.clone();
+            }
+            pow >>= 1;
+            if pow > 0 {
+                square = square.clone() * square;
+            }
         }
         total
     }
@@ -0,0 +1,202 @@
+//! # Rational and real roots of polynomials
+
+use rand::Rng;
+use tracing::debug;
+
+use crate::{
+    ch15::{
+        factorization_over_integers, integer_primitive_part, squarefree_factorization_over_integers,
+    },
+    Group, Integer, Natural, Polynomial, Rational, Ring,
+};
+
+/// The number of sign changes in the coefficient sequence of `f`, ignoring
+/// zeros.
+fn sign_variations(f: &Polynomial<Rational>) -> usize {
+    let signs: Vec<Integer> = f
+        .iter()
+        .map(|(c, _)| c.num.signum())
+        .filter(|s| *s != 0)
+        .collect();
+    signs.windows(2).filter(|w| w[0] != w[1]).count()
+}
+
+/// Descartes' rule of signs for the interval `(a, b)`.
+///
+/// The positive real roots of `(x + 1)ⁿ f((ax + b)/(x + 1))` correspond to
+/// the roots of `f` in `(a, b)`, so its [`sign_variations`] bound their
+/// number and have the same parity. In particular `0` and `1` are exact.
+fn descartes_bound(f: &Polynomial<Rational>, a: Rational, b: Rational) -> usize {
+    let n = f.deg();
+    let num = Polynomial::new(vec![b, a]);
+    let denom = Polynomial::new(vec![Rational::one(), Rational::one()]);
+
+    let g = f.iter().fold(Polynomial::zero(), |g, (c, i)| {
+        g + (num.pow(i) * denom.pow(n - i)).scale(c)
+    });
+    sign_variations(&g)
+}
+
+impl Polynomial<Rational> {
+    /// The distinct rational roots of `self`, in increasing order, or `None`
+    /// if the coefficients are too large for the Hensel lifting.
+    ///
+    /// Every root `p/q` in lowest terms of the primitive `f ∈ Z[x]` gives the
+    /// irreducible factor `qx - p`, so the roots are read off the linear
+    /// factors of the [`factorization_over_integers`]. Unlike testing the
+    /// candidates of the rational root theorem this does not need to factor
+    /// `f(0)` and `lc(f)`. Linear factors of any size are fine, as long as
+    /// [`factorization_over_integers`] succeeds.
+    pub fn roots(&self, rng: &mut impl Rng) -> Option<Vec<Rational>> {
+        assert!(!self.is_zero(), "every element is a root of zero");
+
+        let f = integer_primitive_part(self);
+        let mut roots: Vec<Rational> = factorization_over_integers(&f, rng)?
+            .factors
+            .into_iter()
+            .filter(|(g, _)| g.deg() == 1)
+            .map(|(g, _)| {
+                Rational {
+                    num: -g.coef_at(0),
+                    denom: g.lc() as Natural,
+                }
+                .normalized()
+            })
+            .collect();
+        debug!("rational roots {roots:?}");
+
+        roots.sort();
+        Some(roots)
+    }
+
+    /// Real root isolation with Descartes' rule of signs.
+    ///
+    /// - Input: nonzero `f ∈ Q[x]`.
+    /// - Output: disjoint intervals `(a, b)` in increasing order, each
+    ///   containing exactly one real root of `f`. The roots which happen to
+    ///   be bisection points are returned as `(r, r)`, all other intervals
+    ///   are open.
+    ///
+    /// All real roots of the squarefree part of `f` lie in `(-M, M)` for the
+    /// Cauchy bound `M = 1 + max |fᵢ/lc(f)|`. This interval is bisected
+    /// until each piece has `0` or `1` sign variations in Descartes' rule.
+    pub fn isolate_real_roots(&self) -> Vec<(Rational, Rational)> {
+        let scope = tracing::span!(
+            tracing::Level::DEBUG,
+            "Real root isolation",
+            f = format!("{self:?}"),
+        );
+        let _enter = scope.enter();
+
+        assert!(!self.is_zero(), "every element is a root of zero");
+        if self.deg() == 0 {
+            return vec![];
+        }
+
+        let f = squarefree_factorization_over_integers(&integer_primitive_part(self))
+            .into_iter()
+            .fold(Polynomial::one(), |f, a| f * a);
+        let f = Polynomial::<Rational>::from(f);
+        let lc = f.lc();
+        let m = f.iter().map(|(c, _)| (*c / lc).abs()).max().unwrap() + Rational::one();
+        debug!("squarefree part {f:?}, M = {m:?}");
+
+        fn isolate(
+            f: &Polynomial<Rational>,
+            a: Rational,
+            b: Rational,
+        ) -> Vec<(Rational, Rational)> {
+            let v = descartes_bound(f, a, b);
+            debug!("({a:?}, {b:?}): {v} sign variations");
+
+            match v {
+                0 => vec![],
+                1 => vec![(a, b)],
+                _ => {
+                    let mid = (a + b) / Rational::from(2);
+                    let mut intervals = isolate(f, a, mid);
+                    if f.evaluate_at(mid).is_zero() {
+                        intervals.push((mid, mid));
+                    }
+                    intervals.extend(isolate(f, mid, b));
+                    intervals
+                }
+            }
+        }
+
+        isolate(&f, -m, m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn poly(cs: &[Integer]) -> Polynomial<Rational> {
+        Polynomial::new(cs.iter().copied().map(Rational::from).collect())
+    }
+
+    #[test]
+    fn rational_roots() {
+        let mut rng = StdRng::seed_from_u64(34);
+
+        // x(2x - 1)(x + 3)²(x² - 2)
+        let f = poly(&[0, 1]) * poly(&[-1, 2]) * poly(&[3, 1]).pow(2) * poly(&[-2, 0, 1]);
+
+        assert_eq!(
+            f.roots(&mut rng).unwrap(),
+            [
+                Rational::from(-3),
+                Rational::zero(),
+                Rational { num: 1, denom: 2 }
+            ]
+        );
+        assert_eq!(poly(&[1, 0, 1]).roots(&mut rng).unwrap(), []);
+
+        // the divisors of f(0) are out of reach for trial division
+        let big = 10i128.pow(30);
+        assert_eq!(
+            poly(&[-big, 1]).roots(&mut rng).unwrap(),
+            [Rational::from(big)]
+        );
+
+        let f = poly(&[-(1 << 40), 3]) * poly(&[7, 0, 1]);
+        assert_eq!(
+            f.roots(&mut rng).unwrap(),
+            [Rational {
+                num: 1 << 40,
+                denom: 3
+            }]
+        );
+
+        // (x - 2)(x⁶⁴ + x + 1) is too large for the Hensel lifting
+        let mut cs = vec![0; 65];
+        (cs[0], cs[1], cs[64]) = (1, 1, 1);
+        let f = poly(&[-2, 1]) * poly(&cs);
+        assert_eq!(f.roots(&mut rng), None);
+    }
+
+    #[test]
+    fn real_root_isolation() {
+        // (x² - 2)(x - 1)²(3x + 1)
+        let f = poly(&[-2, 0, 1]) * poly(&[-1, 1]).pow(2) * poly(&[1, 3]);
+        let intervals = f.isolate_real_roots();
+
+        let roots = [-2f64.sqrt(), -1.0 / 3.0, 1.0, 2f64.sqrt()];
+        assert_eq!(intervals.len(), roots.len());
+        for ((a, b), root) in intervals.iter().zip(roots) {
+            let (a, b) = (a.num as f64 / a.denom as f64, b.num as f64 / b.denom as f64);
+            assert!(a < root && root < b || a == root && root == b);
+        }
+        assert!(intervals.windows(2).all(|w| w[0].1 <= w[1].0));
+
+        // x³ - x, where the bisection hits the root 0
+        let intervals = poly(&[0, -1, 0, 1]).isolate_real_roots();
+        assert_eq!(intervals.len(), 3);
+        assert_eq!(intervals[1], (Rational::zero(), Rational::zero()));
+
+        assert_eq!(poly(&[1, 0, 1]).isolate_real_roots(), []);
+    }
+}
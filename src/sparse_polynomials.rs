@@ -0,0 +1,350 @@
+//! # Sparse univariate polynomials
+
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+
+use crate::{
+    field::Field,
+    group::AbelianGroup,
+    identity::{Addition, Identity, Multiplication},
+    Group, Natural, Polynomial, Ring,
+};
+
+/// A univariate polynomial stored as its nonzero terms, so that for example
+/// `x^1000000 + 1` takes two terms instead of a million coefficients.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SparsePolynomial<F> {
+    /// The terms `(e, c)` of `c xᵉ` with `c ≠ 0` in increasing order of `e`,
+    /// that is:
+    /// ```ignore
+    /// ax^100 + b == [(0, b), (100, a)]
+    /// ```
+    terms: Vec<(Natural, F)>,
+}
+
+impl<F> std::fmt::Debug for SparsePolynomial<F>
+where
+    F: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.terms.is_empty() {
+            write!(f, "0")
+        } else {
+            write!(
+                f,
+                "{}",
+                self.terms
+                    .iter()
+                    .map(|(e, c)| match e {
+                        0 => format!("{c:?}"),
+                        1 => format!("{c:?}x"),
+                        _ => format!("{c:?}x^{e}"),
+                    })
+                    .rev()
+                    .format(" + ")
+            )
+        }
+    }
+}
+
+/// `xᵉ` by repeated squaring, with `mul` as the multiplication.
+fn power<R: Clone>(x: &R, mut e: Natural, one: R, mul: impl Fn(&R, &R) -> R) -> R {
+    let mut result = one;
+    let mut square = x.clone();
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul(&result, &square);
+        }
+        e >>= 1;
+        if e > 0 {
+            square = mul(&square, &square);
+        }
+    }
+    result
+}
+
+impl<F: Group> SparsePolynomial<F> {
+    /// The polynomial `Σ c xᵉ` of the given terms, in any order and with
+    /// repeated exponents allowed.
+    pub fn new(terms: impl IntoIterator<Item = (Natural, F)>) -> Self {
+        let mut collected: BTreeMap<Natural, F> = BTreeMap::new();
+        for (e, c) in terms {
+            let entry = collected.entry(e).or_insert_with(F::zero);
+            *entry = entry.clone() + c;
+        }
+
+        SparsePolynomial {
+            terms: collected
+                .into_iter()
+                .filter(|(_, c)| !c.is_zero())
+                .collect(),
+        }
+    }
+
+    /// The monomial `c xᵉ`.
+    pub fn monomial(c: F, e: Natural) -> Self {
+        Self::new([(e, c)])
+    }
+
+    pub fn x() -> Self
+    where
+        F: Ring,
+    {
+        Self::monomial(F::one(), 1)
+    }
+
+    /// The terms `(e, c)` of `c xᵉ` with `c ≠ 0`, in increasing order of `e`.
+    pub fn terms(&self) -> &[(Natural, F)] {
+        &self.terms
+    }
+
+    pub fn deg(&self) -> Natural {
+        self.terms.last().map(|(e, _)| *e).unwrap_or(0)
+    }
+
+    pub fn lc(&self) -> F {
+        self.terms
+            .last()
+            .map(|(_, c)| c.clone())
+            .unwrap_or_else(F::zero)
+    }
+
+    pub fn coef_at(&self, e: Natural) -> F {
+        self.terms
+            .binary_search_by_key(&e, |(e, _)| *e)
+            .map(|i| self.terms[i].1.clone())
+            .unwrap_or_else(|_| F::zero())
+    }
+
+    pub fn scale(&self, s: &F) -> Self
+    where
+        F: Ring,
+    {
+        Self::new(self.terms.iter().map(|(e, c)| (*e, c.clone() * s.clone())))
+    }
+
+    pub fn times_x(&self, pow: Natural) -> Self {
+        SparsePolynomial {
+            terms: self
+                .terms
+                .iter()
+                .map(|(e, c)| (e + pow, c.clone()))
+                .collect(),
+        }
+    }
+
+    /// Evaluates `self` at `x`, computing each `x^(eᵢ₊₁ - eᵢ)` between
+    /// consecutive terms by repeated squaring.
+    pub fn evaluate_at(&self, x: impl Into<F>) -> F
+    where
+        F: Ring,
+    {
+        let x = x.into();
+        self.evaluate_with(&x, |c| c.clone(), |a, b| a.clone() * b.clone())
+    }
+
+    /// Evaluates `self` at `x` in a ring `R` with the multiplication `mul`,
+    /// where the coefficients are embedded with `embed`.
+    fn evaluate_with<R: Group>(
+        &self,
+        x: &R,
+        embed: impl Fn(&F) -> R,
+        mul: impl Fn(&R, &R) -> R,
+    ) -> R
+    where
+        F: Ring,
+    {
+        let mut sum = R::zero();
+        let mut last = 0;
+        let mut x_e = embed(&F::one());
+        for (e, c) in &self.terms {
+            x_e = power(x, e - last, x_e, &mul);
+            last = *e;
+            sum = sum + mul(&embed(c), &x_e);
+        }
+        sum
+    }
+
+    /// `self rem f`, reducing each term `c xᵉ` with `xᵉ rem f` computed by
+    /// repeated squaring modulo `f`.
+    pub fn rem(&self, f: &Polynomial<F>) -> Polynomial<F>
+    where
+        F: Field,
+    {
+        let x = Polynomial::x() % f.clone();
+        self.evaluate_with(
+            &x,
+            |c| Polynomial::new(vec![c.clone()]),
+            |a, b| (a * b) % f.clone(),
+        )
+    }
+
+    /// Modular exponentiation `selfᵉ rem f` with [`Polynomial::pow_mod`]
+    /// after reducing `self`, so that for example `x^(pᵏ) rem f` only takes
+    /// `O(k log p)` multiplications modulo `f`.
+    pub fn pow_mod(&self, e: Natural, f: &Polynomial<F>) -> Polynomial<F>
+    where
+        F: Field,
+    {
+        self.rem(f).pow_mod(e, f)
+    }
+}
+
+impl<F: Group> From<Polynomial<F>> for SparsePolynomial<F> {
+    fn from(f: Polynomial<F>) -> Self {
+        SparsePolynomial {
+            terms: f
+                .iter()
+                .filter(|(c, _)| !c.is_zero())
+                .map(|(c, e)| (e, c.clone()))
+                .collect(),
+        }
+    }
+}
+impl<F: Group> From<SparsePolynomial<F>> for Polynomial<F> {
+    fn from(f: SparsePolynomial<F>) -> Self {
+        let mut coefficients = vec![F::zero(); f.terms.last().map_or(0, |(e, _)| *e as usize + 1)];
+        for (e, c) in f.terms {
+            coefficients[e as usize] = c;
+        }
+        Polynomial::new(coefficients)
+    }
+}
+
+impl<F: Group> std::ops::Add for SparsePolynomial<F> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        SparsePolynomial {
+            terms: self
+                .terms
+                .into_iter()
+                .merge_join_by(rhs.terms, |(e, _), (f, _)| e.cmp(f))
+                .filter_map(|terms| match terms {
+                    itertools::EitherOrBoth::Both((e, l), (_, r)) => {
+                        Some((e, l + r)).filter(|(_, c)| !c.is_zero())
+                    }
+                    itertools::EitherOrBoth::Left(t) | itertools::EitherOrBoth::Right(t) => Some(t),
+                })
+                .collect(),
+        }
+    }
+}
+impl<F: Group> std::ops::Neg for SparsePolynomial<F> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        SparsePolynomial {
+            terms: self.terms.into_iter().map(|(e, c)| (e, -c)).collect(),
+        }
+    }
+}
+impl<F: Group> std::ops::Sub for SparsePolynomial<F> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+impl<F: Ring> std::ops::Mul for SparsePolynomial<F> {
+    type Output = Self;
+
+    /// The `s·t` products of the terms, collected by exponent.
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.terms
+                .iter()
+                .cartesian_product(&rhs.terms)
+                .map(|((e, a), (f, b))| (e + f, a.clone() * b.clone())),
+        )
+    }
+}
+
+impl<F: Group> Identity<Addition> for SparsePolynomial<F> {
+    fn identity() -> Self {
+        SparsePolynomial { terms: vec![] }
+    }
+}
+impl<F: Group> Group for SparsePolynomial<F> {}
+impl<F: Group> AbelianGroup for SparsePolynomial<F> {}
+
+impl<F: Ring> Identity<Multiplication> for SparsePolynomial<F> {
+    fn identity() -> Self {
+        Self::monomial(F::one(), 0)
+    }
+}
+impl<F: Ring> Ring for SparsePolynomial<F> {
+    fn multiplicative_inverse(&self) -> Option<Self> {
+        match self.terms.as_slice() {
+            [(0, c)] => Some(Self::monomial(c.multiplicative_inverse()?, 0)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Finite, Integer, Rational};
+
+    use super::*;
+
+    fn sparse(terms: &[(Natural, Integer)]) -> SparsePolynomial<Integer> {
+        SparsePolynomial::new(terms.iter().copied())
+    }
+
+    #[test]
+    fn arithmetic() {
+        let f = sparse(&[(1_000_000, 1), (0, 1)]);
+        let g = sparse(&[(1_000_000, 1), (0, -1)]);
+
+        assert_eq!(f.terms().len(), 2);
+        assert_eq!(format!("{f:?}"), "1x^1000000 + 1");
+        assert_eq!(f.clone() * g.clone(), sparse(&[(2_000_000, 1), (0, -1)]));
+        assert_eq!(f.clone() - g.clone(), sparse(&[(0, 2)]));
+        assert_eq!(f.clone() + -f.clone(), SparsePolynomial::zero());
+        assert_eq!(f.times_x(3).deg(), 1_000_003);
+        assert_eq!(f.coef_at(1_000_000), 1);
+        assert_eq!(f.coef_at(5), 0);
+
+        // agrees with the dense multiplication
+        let a = Polynomial::new(vec![1, 0, -2, 3]);
+        let b = Polynomial::new(vec![0, 5, 0, 0, 1]);
+        assert_eq!(
+            Polynomial::from(SparsePolynomial::from(a.clone()) * SparsePolynomial::from(b.clone())),
+            a * b
+        );
+    }
+
+    #[test]
+    fn evaluation() {
+        // 2^1000000 + 1 ≡ 2^(1000000 mod 100) + 1 = 2 mod 101 by Fermat
+        let f = SparsePolynomial::new([(1_000_000, Finite::<101>::one()), (0, Finite::one())]);
+        assert_eq!(f.evaluate_at(Finite::from(2i128)), Finite::from(2i128));
+
+        let f = SparsePolynomial::new([(3, Rational::from(2)), (10, Rational::one())]);
+        let dense = Polynomial::from(f.clone());
+        for x in [-2, 0, 3].map(Rational::from) {
+            assert_eq!(f.evaluate_at(x), dense.evaluate_at(x));
+        }
+    }
+
+    #[test]
+    fn modular_exponentiation() {
+        // x³ - 2 is irreducible over F₇, so x^(7³) ≡ x mod x³ - 2
+        let f = Polynomial::new([-2i128, 0, 0, 1].map(Finite::<7>::from).to_vec());
+        let x = SparsePolynomial::<Finite<7>>::x();
+
+        assert_eq!(x.pow_mod(7 * 7 * 7, &f), Polynomial::x());
+        assert_ne!(x.pow_mod(7, &f), Polynomial::x());
+        assert_eq!(
+            SparsePolynomial::monomial(Finite::one(), 7 * 7 * 7).rem(&f),
+            Polynomial::x()
+        );
+
+        let g = SparsePolynomial::new([(2, Finite::<7>::one()), (0, Finite::from(3i128))]);
+        let expected =
+            (0..5).fold(Polynomial::one(), |p, _| p * Polynomial::from(g.clone())) % f.clone();
+        assert_eq!(g.pow_mod(5, &f), expected);
+    }
+}
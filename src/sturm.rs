@@ -0,0 +1,233 @@
+//! # Sturm sequences
+
+use tracing::debug;
+
+use crate::{Group, Integer, Polynomial, Rational, Ring};
+
+/// The Sturm sequence `f₀, ..., fₗ` of `f ∈ Q[x]` together with the quotients
+/// `qᵢ` of `fᵢ₋₁ = qᵢ fᵢ - fᵢ₊₁`, where `f₀` is the squarefree part of `f`.
+#[derive(Debug, Clone)]
+pub struct SturmSequence {
+    pub chain: Vec<Polynomial<Rational>>,
+    pub q: Vec<Polynomial<Rational>>,
+}
+
+impl std::fmt::Display for SturmSequence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use comfy_table::{
+            modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL, Attribute, Cell, ContentArrangement,
+            Table,
+        };
+
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS)
+            .set_content_arrangement(ContentArrangement::Dynamic);
+
+        table.set_header(["i", "q", "f"].map(|t| Cell::new(t).add_attribute(Attribute::Bold)));
+
+        for (i, fi) in self.chain.iter().enumerate() {
+            table.add_row([
+                format!("{i}"),
+                self.q.get(i).map(|q| format!("{q:?}")).unwrap_or_default(),
+                format!("{fi:?}"),
+            ]);
+        }
+
+        write!(f, "{table}")
+    }
+}
+
+/// The Sturm sequence of `f ∈ Q[x]`.
+///
+/// Starting from `f₀ = f` and `f₁ = f'`, each `fᵢ₊₁ = -(fᵢ₋₁ rem fᵢ)` is the
+/// negated remainder, until it vanishes. The last element `fₗ` is then
+/// `gcd(f, f')` up to a constant. If it is not constant, every `fᵢ` is divided
+/// by it, which keeps the quotients and gives the Sturm sequence of the
+/// squarefree part of `f`. Otherwise all `fᵢ` would vanish at the multiple
+/// roots of `f`, and these would be miscounted as endpoints.
+pub fn sturm_sequence(f: &Polynomial<Rational>) -> SturmSequence {
+    let scope = tracing::span!(
+        tracing::Level::DEBUG,
+        "Sturm sequence",
+        f = format!("{f:?}"),
+    );
+    let _enter = scope.enter();
+
+    let mut chain = vec![f.clone()];
+    let mut q = vec![Polynomial::zero()];
+
+    let mut next = f.diff();
+    while !next.is_zero() {
+        let (qi, r) = chain.last().unwrap().div_rem(&next).unwrap();
+        debug!("f{} = {next:?}, q{} = {qi:?}", chain.len(), chain.len());
+        chain.push(next);
+        q.push(qi);
+        next = -r.normalized();
+    }
+
+    let gcd = chain.last().unwrap().clone();
+    if gcd.deg() > 0 {
+        debug!("gcd(f, f') = {gcd:?}");
+        for fi in &mut chain {
+            *fi = fi.div_rem(&gcd).unwrap().0;
+        }
+    }
+
+    SturmSequence { chain, q }
+}
+
+/// The number of sign changes in `signs`, ignoring zeros.
+fn variations(signs: impl IntoIterator<Item = Integer>) -> usize {
+    let signs: Vec<Integer> = signs.into_iter().filter(|s| *s != 0).collect();
+    signs.windows(2).filter(|w| w[0] != w[1]).count()
+}
+
+impl SturmSequence {
+    /// The number `V(a)` of sign variations in `f₀(a), ..., fₗ(a)`.
+    pub fn variations_at(&self, a: Rational) -> usize {
+        variations(self.chain.iter().map(|f| f.evaluate_at(a).num.signum()))
+    }
+
+    /// Sturm's theorem: the number `V(a) - V(b)` of distinct real roots of
+    /// `f` in the half-open interval `(a, b]`, for `a < b`.
+    ///
+    /// As `f₀` is squarefree, `V` never increases and the difference is
+    /// nonnegative, also when `a` or `b` is a multiple root of `f`.
+    pub fn count_roots(&self, a: Rational, b: Rational) -> usize {
+        assert!(a < b, "({a:?}, {b:?}] is empty");
+
+        self.variations_at(a) - self.variations_at(b)
+    }
+
+    /// The number `V(-∞) - V(∞)` of distinct real roots of `f`, where the
+    /// signs at `±∞` are those of the leading terms.
+    pub fn count_real_roots(&self) -> usize {
+        let at_infinity = self.chain.iter().map(|f| f.lc().num.signum());
+        let at_minus_infinity = self
+            .chain
+            .iter()
+            .map(|f| f.lc().num.signum() * if f.deg() % 2 == 1 { -1 } else { 1 });
+
+        variations(at_minus_infinity) - variations(at_infinity)
+    }
+
+    /// Isolates the distinct real roots of `f` by bisection.
+    ///
+    /// - Input: `width > 0`.
+    /// - Output: disjoint half-open intervals `(a, b]` with `b - a ≤ width`
+    ///   in increasing order, each containing exactly one real root of `f`.
+    ///
+    /// Bisection starts from `(-M, M]` for the Cauchy bound
+    /// `M = 1 + max |fᵢ/lc(f)|` and discards intervals without roots.
+    pub fn isolate_roots(&self, width: Rational) -> Vec<(Rational, Rational)> {
+        let scope = tracing::span!(
+            tracing::Level::DEBUG,
+            "Sturm root isolation",
+            f = format!("{:?}", self.chain[0]),
+            width = format!("{width:?}"),
+        );
+        let _enter = scope.enter();
+
+        assert!(width > Rational::zero(), "the width must be positive");
+
+        let f = &self.chain[0];
+        if f.deg() == 0 {
+            return vec![];
+        }
+        let lc = f.lc();
+        let m = f.iter().map(|(c, _)| (*c / lc).abs()).max().unwrap() + Rational::one();
+
+        let mut intervals = vec![];
+        let mut stack = vec![(-m, m)];
+        while let Some((a, b)) = stack.pop() {
+            let count = self.count_roots(a, b);
+            debug!("({a:?}, {b:?}]: {count} roots");
+
+            if count == 1 && b - a <= width {
+                intervals.push((a, b));
+            } else if count > 0 {
+                let mid = (a + b) / Rational::from(2);
+                stack.push((mid, b));
+                stack.push((a, mid));
+            }
+        }
+
+        intervals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poly(cs: &[Integer]) -> Polynomial<Rational> {
+        Polynomial::new(cs.iter().copied().map(Rational::from).collect())
+    }
+
+    #[test]
+    fn counting() {
+        // x³ - 3x + 1 has roots near -1.88, 0.35 and 1.53
+        let sturm = sturm_sequence(&poly(&[1, -3, 0, 1]));
+
+        assert_eq!(sturm.chain.len(), 4);
+        assert_eq!(sturm.count_real_roots(), 3);
+        assert_eq!(sturm.count_roots(Rational::from(-2), Rational::from(2)), 3);
+        assert_eq!(sturm.count_roots(Rational::zero(), Rational::one()), 1);
+        assert_eq!(sturm.count_roots(Rational::from(2), Rational::from(5)), 0);
+
+        // (x - 1)²(x + 2)
+        let sturm = sturm_sequence(&(poly(&[-1, 1]).pow(2) * poly(&[2, 1])));
+        assert_eq!(sturm.count_real_roots(), 2);
+        assert_eq!(sturm.count_roots(Rational::zero(), Rational::one()), 1);
+        assert_eq!(sturm.count_roots(Rational::one(), Rational::from(3)), 0);
+
+        assert_eq!(sturm_sequence(&poly(&[1, 0, 1])).count_real_roots(), 0);
+    }
+
+    #[test]
+    fn multiple_roots_at_endpoints() {
+        // (x - 1)²(x - 2)
+        let sturm = sturm_sequence(&(poly(&[-1, 1]).pow(2) * poly(&[-2, 1])));
+        let f0 = &sturm.chain[0];
+        assert_eq!(*f0, poly(&[2, -3, 1]).scale(&f0.lc()));
+        assert_eq!(sturm.chain.last().unwrap().deg(), 0);
+        assert_eq!(sturm.count_roots(Rational::one(), Rational::from(3)), 1);
+        assert_eq!(sturm.count_roots(Rational::zero(), Rational::one()), 1);
+
+        // x²(x - 1)
+        let sturm = sturm_sequence(&(poly(&[0, 0, 1]) * poly(&[-1, 1])));
+        assert_eq!(sturm.count_real_roots(), 2);
+        assert_eq!(sturm.count_roots(Rational::zero(), Rational::from(2)), 1);
+        assert_eq!(sturm.count_roots(-Rational::one(), Rational::zero()), 1);
+
+        let width = Rational { num: 1, denom: 10 };
+        let intervals = sturm.isolate_roots(width);
+        assert_eq!(intervals.len(), 2);
+        for ((a, b), root) in intervals.into_iter().zip([0, 1]) {
+            assert!(a < Rational::from(root) && Rational::from(root) <= b);
+        }
+    }
+
+    #[test]
+    fn isolation() {
+        let width = Rational { num: 1, denom: 100 };
+        let intervals = sturm_sequence(&poly(&[-2, 0, 1])).isolate_roots(width);
+
+        assert_eq!(intervals.len(), 2);
+        for ((a, b), root) in intervals.iter().zip([-2f64.sqrt(), 2f64.sqrt()]) {
+            assert!(*b - *a <= width);
+            let (a, b) = (a.num as f64 / a.denom as f64, b.num as f64 / b.denom as f64);
+            assert!(a < root && root <= b);
+        }
+    }
+
+    #[test]
+    fn table() {
+        let table = sturm_sequence(&poly(&[1, -3, 0, 1])).to_string();
+
+        assert!(table.contains("1x^3 + -3x + 1"));
+        assert!(table.contains("3x^2 + -3"));
+    }
+}